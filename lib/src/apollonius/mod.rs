@@ -0,0 +1,284 @@
+#![allow(clippy::many_single_char_names)]
+use d3_geo_rs::cartesian::cross;
+use d3_geo_rs::cartesian::dot;
+use d3_geo_rs::cartesian::normalize;
+use d3_geo_rs::cartesian::spherical;
+use geo::CoordFloat;
+use geo_types::Coord;
+use num_traits::FloatConst;
+
+use crate::delaunay::cartesian::cartesian;
+use crate::error::ConstructionError;
+use crate::error::Rejection;
+use crate::error::WeightError;
+
+mod polygons;
+
+/// The number of rays cast from each site by [`Apollonius::try_new`].
+pub const DEFAULT_RESOLUTION: usize = 64;
+
+/// An additively weighted ( Apollonius ) Voronoi diagram on the sphere.
+///
+/// Each site is a spherical cap, the distance from a point to a site is
+/// the geodesic distance to the boundary of its cap, negative inside the
+/// cap. A large cap claims territory from its smaller neighbors, and a
+/// cap lying inside another has an empty cell.
+///
+/// The cells are bounded by curves, so each cell is approximated by
+/// casting rays from its site and locating where each ray leaves the
+/// cell. Cells are star shaped about their site, so one crossing per ray
+/// suffices. The cost is `O(n * n * resolution)`.
+///
+/// ```
+/// use geo_types::Coord;
+/// use d3_geo_voronoi_rs::apollonius::Apollonius;
+///
+/// let sites = [
+///     Coord { x: 0f64, y: 0f64 },
+///     Coord { x: 20f64, y: 0f64 },
+///     Coord { x: 1f64, y: 0f64 },
+/// ];
+/// // The third cap lies inside the first.
+/// let radii = [0.2f64, 0.05, 0.01];
+/// let apollonius = Apollonius::try_new(&sites, &radii).unwrap();
+/// assert_eq!(apollonius.covered(), &[2]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Apollonius<T>
+where
+    T: CoordFloat,
+{
+    /// The sites, in input order.
+    pub points: Vec<Coord<T>>,
+    /// The radius of each site's cap, in radians.
+    pub radii: Vec<T>,
+    /// The boundary of each cell, clockwise as the rings of
+    /// `Voronoi::polygons()`, without repeating the first point.
+    ///
+    /// Empty when the site was rejected or is covered.
+    pub polygons: Vec<Vec<Coord<T>>>,
+    /// The sites sharing a boundary with each site, as sampled by the
+    /// rays.
+    pub neighbors: Vec<Vec<usize>>,
+    covered: Vec<usize>,
+    rejected: Vec<(usize, Rejection)>,
+}
+
+impl<T> Apollonius<T>
+where
+    T: CoordFloat + FloatConst,
+{
+    /// Computes the diagram of the sites, one cap radius per site, with
+    /// [`DEFAULT_RESOLUTION`] rays per cell.
+    ///
+    /// # Errors
+    ///  See [`Apollonius::try_with_resolution`].
+    pub fn try_new(
+        sites: &[Coord<T>],
+        radii: &[T],
+    ) -> Result<Self, WeightError> {
+        Self::try_with_resolution(sites, radii, DEFAULT_RESOLUTION)
+    }
+
+    /// Computes the diagram of the sites, one cap radius per site, casting
+    /// `resolution` rays ( at least 3 ) per cell.
+    ///
+    /// Radii are in radians, in `[0, PI)`. Sites with a non-finite
+    /// coordinate are rejected, see [`Apollonius::rejected`].
+    ///
+    /// # Errors
+    ///  When the radii do not match the sites, when a radius is out of
+    ///  range, or when fewer than two sites are valid.
+    ///
+    /// # Panics
+    ///  Will never happen as constants will always be converted into T.
+    pub fn try_with_resolution(
+        sites: &[Coord<T>],
+        radii: &[T],
+        resolution: usize,
+    ) -> Result<Self, WeightError> {
+        if sites.len() != radii.len() {
+            return Err(WeightError::CountMismatch {
+                sites: sites.len(),
+                weights: radii.len(),
+            });
+        }
+        if let Some(site) = radii
+            .iter()
+            .position(|r| !(*r >= T::zero() && *r < T::PI()))
+        {
+            return Err(WeightError::InvalidWeight { site });
+        }
+
+        let mut rejected = Vec::new();
+        let vectors: Vec<[T; 3]> = sites
+            .iter()
+            .enumerate()
+            .map(|(i, site)| {
+                if !(site.x + site.y).is_finite() {
+                    rejected.push((i, Rejection::NonFinite));
+                }
+                cartesian(site)
+            })
+            .collect();
+        let valid: Vec<usize> = (0..sites.len())
+            .filter(|&i| (sites[i].x + sites[i].y).is_finite())
+            .collect();
+        if valid.is_empty() && !sites.is_empty() {
+            return Err(ConstructionError::AllSitesNonFinite.into());
+        }
+        if valid.len() < 2 {
+            return Err(ConstructionError::TooFewSites {
+                got: valid.len(),
+                needed: 2,
+            }
+            .into());
+        }
+
+        let epsilon = T::epsilon() * T::from(64_f64).unwrap();
+        let angle = |a: &[T; 3], b: &[T; 3]| {
+            dot(a, b).max(-T::one()).min(T::one()).acos()
+        };
+        // A cap inside another has no cell, of two identical caps the
+        // first keeps its cell.
+        let covered: Vec<usize> = valid
+            .iter()
+            .copied()
+            .filter(|&i| {
+                valid.iter().any(|&j| {
+                    let slack =
+                        radii[j] - radii[i] - angle(&vectors[i], &vectors[j]);
+                    j != i && (slack > epsilon || (slack >= -epsilon && j < i))
+                })
+            })
+            .collect();
+        let competitors: Vec<usize> = valid
+            .iter()
+            .copied()
+            .filter(|i| !covered.contains(i))
+            .collect();
+
+        let mut polygons = vec![Vec::new(); sites.len()];
+        let mut neighbors = vec![Vec::new(); sites.len()];
+        for &i in &competitors {
+            (polygons[i], neighbors[i]) =
+                cell(i, &competitors, &vectors, radii, resolution.max(3));
+        }
+
+        Ok(Self {
+            points: sites.to_vec(),
+            radii: radii.to_vec(),
+            polygons,
+            neighbors,
+            covered,
+            rejected,
+        })
+    }
+}
+
+/// Casts `resolution` rays from the site `i`, returning the boundary of
+/// its cell and its neighbors.
+fn cell<T>(
+    i: usize,
+    competitors: &[usize],
+    vectors: &[[T; 3]],
+    radii: &[T],
+    resolution: usize,
+) -> (Vec<Coord<T>>, Vec<usize>)
+where
+    T: CoordFloat + FloatConst,
+{
+    let step = T::TAU() / T::from(resolution).unwrap();
+    let p = vectors[i];
+    // A tangent basis at the site.
+    let axis = if p[2].abs() < T::from(0.9_f64).unwrap() {
+        [T::zero(), T::zero(), T::one()]
+    } else {
+        [T::one(), T::zero(), T::zero()]
+    };
+    let e1 = normalize(&cross(&axis, &p));
+    let e2 = cross(&p, &e1);
+
+    let mut boundary = Vec::with_capacity(resolution);
+    let mut neighbors = Vec::new();
+    for k in 0..resolution {
+        // Clockwise seen from outside.
+        let theta = -step * T::from(k).unwrap();
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        let u = [
+            e1[0] * cos_theta + e2[0] * sin_theta,
+            e1[1] * cos_theta + e2[1] * sin_theta,
+            e1[2] * cos_theta + e2[2] * sin_theta,
+        ];
+        let mut t = T::PI();
+        let mut neighbor = None;
+        for &j in competitors {
+            if j == i {
+                continue;
+            }
+            if let Some(tj) = crossing(&p, &u, &vectors[j], radii[i] - radii[j])
+            {
+                if tj < t {
+                    t = tj;
+                    neighbor = Some(j);
+                }
+            }
+        }
+        let (sin_t, cos_t) = t.sin_cos();
+        boundary.push(spherical(&[
+            p[0] * cos_t + u[0] * sin_t,
+            p[1] * cos_t + u[1] * sin_t,
+            p[2] * cos_t + u[2] * sin_t,
+        ]));
+        if let Some(j) = neighbor {
+            if !neighbors.contains(&j) {
+                neighbors.push(j);
+            }
+        }
+    }
+    (boundary, neighbors)
+}
+
+/// Along the ray leaving the site `p` in the direction `u`, returns the
+/// distance at which the site `q` becomes as close, where `delta` is the
+/// radius of `p` less the radius of `q`.
+///
+/// With `x(t) = p cos(t) + u sin(t)` the crossing solves
+/// `cos(d(x(t), q)) = cos(t - delta)`, which is linear in `cos(t)` and
+/// `sin(t)`, and holds for the distances themselves when `t - delta` is
+/// in `[0, PI]`.
+fn crossing<T>(p: &[T; 3], u: &[T; 3], q: &[T; 3], delta: T) -> Option<T>
+where
+    T: CoordFloat + FloatConst,
+{
+    let (sin_delta, cos_delta) = delta.sin_cos();
+    let y = cos_delta - dot(p, q);
+    let x = dot(u, q) - sin_delta;
+    if x == T::zero() && y == T::zero() {
+        return None;
+    }
+    let mut t = y.atan2(x);
+    if t < T::zero() {
+        t = t + T::PI();
+    }
+    let d = t - delta;
+    (t > T::zero() && d >= T::zero() && d <= T::PI()).then_some(t)
+}
+
+impl<T> Apollonius<T>
+where
+    T: CoordFloat,
+{
+    /// The sites whose cap lies inside another cap, and so have an empty
+    /// cell.
+    #[must_use]
+    pub fn covered(&self) -> &[usize] {
+        &self.covered
+    }
+
+    /// The sites which were rejected, with the reason for each.
+    #[must_use]
+    pub fn rejected(&self) -> &[(usize, Rejection)] {
+        &self.rejected
+    }
+}
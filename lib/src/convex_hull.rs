@@ -0,0 +1,348 @@
+#![allow(clippy::many_single_char_names)]
+use std::collections::HashMap;
+
+use geo::CoordFloat;
+
+use d3_geo_rs::cartesian::cross;
+use d3_geo_rs::cartesian::dot;
+
+/// A face, its vertices counterclockwise when seen from outside.
+#[derive(Debug)]
+struct Face<T> {
+    v: [usize; 3],
+    normal: [T; 3],
+    offset: T,
+    alive: bool,
+    /// The points yet to be inserted which see this face, and no face
+    /// created before it.
+    outside: Vec<usize>,
+}
+
+fn sub<T: CoordFloat>(a: &[T; 3], b: &[T; 3]) -> [T; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn norm<T: CoordFloat>(a: &[T; 3]) -> T {
+    dot(a, a).sqrt()
+}
+
+impl<T> Face<T>
+where
+    T: CoordFloat,
+{
+    fn new(v: [usize; 3], points: &[[T; 3]]) -> Self {
+        let [a, b, c] = v.map(|i| points[i]);
+        let n = cross(&sub(&b, &a), &sub(&c, &a));
+        let l = norm(&n);
+        // A sliver face is never visible.
+        let normal = if l > T::zero() {
+            [n[0] / l, n[1] / l, n[2] / l]
+        } else {
+            [T::zero(); 3]
+        };
+        Self {
+            v,
+            normal,
+            offset: dot(&normal, &a),
+            alive: true,
+            outside: Vec::new(),
+        }
+    }
+
+    fn height(&self, p: &[T; 3]) -> T {
+        dot(&self.normal, p) - self.offset
+    }
+}
+
+/// The hull under construction.
+struct Hull<'a, T> {
+    points: &'a [[T; 3]],
+    epsilon: T,
+    faces: Vec<Face<T>>,
+    /// Maps each directed edge onto its face.
+    edges: HashMap<(usize, usize), usize>,
+    /// The face on whose conflict list each point waits, None once inside.
+    conflict: Vec<Option<usize>>,
+    visible: Vec<bool>,
+}
+
+impl<T> Hull<'_, T>
+where
+    T: CoordFloat,
+{
+    fn add(&mut self, v: [usize; 3]) {
+        for j in 0..3 {
+            self.edges.insert((v[j], v[(j + 1) % 3]), self.faces.len());
+        }
+        self.faces.push(Face::new(v, self.points));
+    }
+
+    /// Puts the point on the conflict list of the first face it sees,
+    /// from face `first` on.
+    fn assign(&mut self, i: usize, first: usize) {
+        let p = &self.points[i];
+        self.conflict[i] = (first..self.faces.len())
+            .find(|&f| self.faces[f].height(p) > self.epsilon);
+        if let Some(f) = self.conflict[i] {
+            self.faces[f].outside.push(i);
+        }
+    }
+
+    /// The faces point `i` sees, connected around the face on whose
+    /// conflict list it waits.
+    ///
+    /// Faces in the plane of the point are taken too, so that an earlier
+    /// vertex lying on a face of the new hull is dropped.
+    fn region(&mut self, i: usize, start: usize) -> Vec<usize> {
+        let p = &self.points[i];
+        self.visible.resize(self.faces.len(), false);
+        let mut region = vec![start];
+        self.visible[start] = true;
+        let mut k = 0;
+        while k < region.len() {
+            let v = self.faces[region[k]].v;
+            for j in 0..3 {
+                if let Some(&g) = self.edges.get(&(v[(j + 1) % 3], v[j])) {
+                    if !self.visible[g]
+                        && self.faces[g].height(p) > -self.epsilon
+                    {
+                        self.visible[g] = true;
+                        region.push(g);
+                    }
+                }
+            }
+            k += 1;
+        }
+        region
+    }
+
+    /// Replaces the faces point `i` sees by a cone from the point to their
+    /// boundary.
+    fn insert(&mut self, i: usize) {
+        let Some(start) = self.conflict[i] else {
+            return;
+        };
+        let region = self.region(i, start);
+
+        // The boundary of the visible region.
+        let mut horizon: Vec<(usize, usize)> = Vec::new();
+        let mut waiting: Vec<usize> = Vec::new();
+        for &f in &region {
+            let face = &mut self.faces[f];
+            for j in 0..3 {
+                let (a, b) = (face.v[j], face.v[(j + 1) % 3]);
+                if self.edges.get(&(b, a)).is_some_and(|&g| !self.visible[g]) {
+                    horizon.push((a, b));
+                }
+            }
+            face.alive = false;
+            waiting.append(&mut face.outside);
+        }
+        for &f in &region {
+            let v = self.faces[f].v;
+            for j in 0..3 {
+                let e = (v[j], v[(j + 1) % 3]);
+                if self.edges.get(&e) == Some(&f) {
+                    self.edges.remove(&e);
+                }
+            }
+            self.visible[f] = false;
+        }
+        let first = self.faces.len();
+        for (a, b) in horizon {
+            self.add([a, b, i]);
+        }
+
+        // A point which saw a replaced face, and is still outside, sees
+        // one of the new faces.
+        self.conflict[i] = None;
+        for j in waiting {
+            if j != i {
+                self.assign(j, first);
+            }
+        }
+    }
+}
+
+/// Shuffles the points, a fixed seed keeping the hull reproducible.
+fn shuffle(points: &mut [usize]) {
+    let mut seed: u64 = 0x9e37_79b9_7f4a_7c15;
+    for k in (1..points.len()).rev() {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        let j = usize::try_from(seed % (k as u64 + 1)).unwrap_or(0);
+        points.swap(k, j);
+    }
+}
+
+/// Computes the convex hull of a set of 3D points.
+///
+/// Returns the triangular faces, their vertices counterclockwise when
+/// seen from outside. Points inside the hull, or on one of its faces, are
+/// not vertices. Points with a non-finite coordinate are ignored.
+///
+/// None when fewer than four points span a volume.
+///
+/// An incremental construction, in expected O(n log n) time: the points
+/// are inserted in a random order, each waiting in the conflict list of
+/// one face it sees. Inserting a point replaces the faces it sees, found
+/// by walking from that face, and the points waiting on them move onto
+/// the new faces.
+pub fn convex_hull<T>(points: &[[T; 3]]) -> Option<Vec<[usize; 3]>>
+where
+    T: CoordFloat,
+{
+    let finite: Vec<usize> = (0..points.len())
+        .filter(|&i| points[i].iter().all(|x| x.is_finite()))
+        .collect();
+    if finite.len() < 4 {
+        return None;
+    }
+    let scale = finite
+        .iter()
+        .map(|&i| norm(&points[i]))
+        .fold(T::zero(), T::max);
+    let epsilon = T::epsilon() * T::from(64_f64)? * scale;
+
+    // The initial tetrahedron.
+    let argmax = |f: &dyn Fn(&[T; 3]) -> T| {
+        finite
+            .iter()
+            .map(|&i| (i, f(&points[i])))
+            .fold((finite[0], T::zero()), |m, x| if x.1 > m.1 { x } else { m })
+    };
+    let i0 = finite[0];
+    let p0 = points[i0];
+    let (i1, d) = argmax(&|p| norm(&sub(p, &p0)));
+    if d <= epsilon {
+        return None;
+    }
+    let u = sub(&points[i1], &p0);
+    let (i2, d) = argmax(&|p| norm(&cross(&u, &sub(p, &p0))) / norm(&u));
+    if d <= epsilon {
+        return None;
+    }
+    let n = cross(&u, &sub(&points[i2], &p0));
+    let (i3, d) = argmax(&|p| (dot(&n, &sub(p, &p0)) / norm(&n)).abs());
+    if d <= epsilon {
+        return None;
+    }
+
+    let mut hull = Hull {
+        points,
+        epsilon,
+        faces: Vec::new(),
+        edges: HashMap::new(),
+        conflict: vec![None; points.len()],
+        visible: Vec::new(),
+    };
+    let tetrahedron = [i0, i1, i2, i3];
+    let four = T::from(4_f64)?;
+    let centroid = tetrahedron.iter().fold([T::zero(); 3], |c, &i| {
+        [
+            c[0] + points[i][0] / four,
+            c[1] + points[i][1] / four,
+            c[2] + points[i][2] / four,
+        ]
+    });
+    for v in [[i0, i1, i2], [i0, i1, i3], [i0, i2, i3], [i1, i2, i3]] {
+        let face = Face::new(v, points);
+        if face.height(&centroid) > T::zero() {
+            hull.add([v[0], v[2], v[1]]);
+        } else {
+            hull.add(v);
+        }
+    }
+
+    let mut order: Vec<usize> = finite
+        .into_iter()
+        .filter(|i| !tetrahedron.contains(i))
+        .collect();
+    shuffle(&mut order);
+    for &i in &order {
+        hull.assign(i, 0);
+    }
+    for &i in &order {
+        hull.insert(i);
+    }
+
+    Some(
+        hull.faces
+            .into_iter()
+            .filter(|f| f.alive)
+            .map(|f| f.v)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod convex_hull_test {
+    extern crate pretty_assertions;
+
+    use pretty_assertions::assert_eq;
+
+    use d3_geo_rs::cartesian::cross;
+    use d3_geo_rs::cartesian::dot;
+
+    use super::convex_hull;
+    use super::sub;
+
+    #[test]
+    fn cube() {
+        let mut points: Vec<[f64; 3]> = (0..8)
+            .map(|i| {
+                let c = |bit: i32| if i & bit == 0 { -1f64 } else { 1f64 };
+                [c(1), c(2), c(4)]
+            })
+            .collect();
+        // An interior point, and one lying on a face.
+        points.push([0.1f64, 0.2f64, -0.3f64]);
+        points.push([1f64, 0.2f64, 0.3f64]);
+        points.push([f64::NAN, 0f64, 0f64]);
+
+        let faces = convex_hull(&points).unwrap();
+        assert_eq!(faces.len(), 12);
+        for f in &faces {
+            assert!(f.iter().all(|&i| i < 8));
+            // Counterclockwise when seen from outside.
+            let [a, b, c] = f.map(|i| points[i]);
+            let n = cross(&sub(&b, &a), &sub(&c, &a));
+            assert!(dot(&n, &a) > 0f64);
+        }
+    }
+
+    #[test]
+    fn points_on_a_sphere() {
+        // A fibonacci lattice, every point a vertex.
+        let n = 2000;
+        let golden = core::f64::consts::PI * (3f64 - 5f64.sqrt());
+        let points: Vec<[f64; 3]> = (0..n)
+            .map(|i| {
+                let z = 1f64 - 2f64 * (f64::from(i) + 0.5f64) / f64::from(n);
+                let r = (1f64 - z * z).sqrt();
+                let a = golden * f64::from(i);
+                [r * a.cos(), r * a.sin(), z]
+            })
+            .collect();
+
+        let faces = convex_hull(&points).unwrap();
+        assert_eq!(faces.len(), 2 * points.len() - 4);
+        for f in &faces {
+            let [a, b, c] = f.map(|i| points[i]);
+            let n = cross(&sub(&b, &a), &sub(&c, &a));
+            assert!(points.iter().all(|p| dot(&n, &sub(p, &a)) < 1e-12));
+        }
+    }
+
+    #[test]
+    fn coplanar_points_have_no_hull() {
+        let points = vec![
+            [0f64, 0f64, 1f64],
+            [1f64, 0f64, 1f64],
+            [0f64, 1f64, 1f64],
+            [1f64, 1f64, 1f64],
+        ];
+        assert_eq!(convex_hull(&points), None);
+    }
+}
@@ -4,7 +4,7 @@ use geo_types::Coord;
 /// Converts spherical coordinates (degrees) to 3D Cartesian.
 /// Note there is a similar but different function `rust_d3_geo`.
 /// This only difference this one convert to radians first.
-pub(super) fn cartesian<T: CoordFloat>(coordinates: &Coord<T>) -> [T; 3] {
+pub fn cartesian<T: CoordFloat>(coordinates: &Coord<T>) -> [T; 3] {
     let lambda = coordinates.x.to_radians();
     let phi = coordinates.y.to_radians();
     let (sin_phi, cos_phi) = phi.sin_cos();
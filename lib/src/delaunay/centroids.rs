@@ -0,0 +1,99 @@
+use d3_geo_rs::cartesian::cross;
+use d3_geo_rs::cartesian::dot;
+use d3_geo_rs::cartesian::normalize;
+use d3_geo_rs::cartesian::spherical;
+use geo::CoordFloat;
+use geo_types::Coord;
+use num_traits::FloatConst;
+
+use super::cartesian::cartesian;
+use super::clearance::ring_edges;
+use super::excess::area_cartesian;
+use super::within::angle;
+use super::Delaunay;
+
+/// The first moment of a spherical triangle, the integral of the unit
+/// vector over its area.
+///
+/// Its direction is the centroid of the triangle.
+fn moment<T: CoordFloat>(triangle: &[[T; 3]; 3]) -> [T; 3] {
+    let two = T::one() + T::one();
+    let mut m = [T::zero(); 3];
+    for k in 0..3 {
+        let (a, b) = (&triangle[k], &triangle[(k + 1) % 3]);
+        let n = cross(a, b);
+        let length = dot(&n, &n).sqrt();
+        if length > T::zero() {
+            let w = angle(a, b) / length / two;
+            for (mi, ni) in m.iter_mut().zip(n) {
+                *mi = *mi + w * ni;
+            }
+        }
+    }
+    // Whatever the orientation of the triangle.
+    let [a, b, c] = triangle;
+    let center = [a[0] + b[0] + c[0], a[1] + b[1] + c[1], a[2] + b[2] + c[2]];
+    if dot(&m, &center) < T::zero() {
+        m = m.map(|x| -x);
+    }
+    m
+}
+
+impl<T> Delaunay<T>
+where
+    T: CoordFloat + FloatConst,
+{
+    /// Returns the centroid of the cell of each site, as a unit vector,
+    /// and the energy of the tessellation, the sum over the cells of the
+    /// integral of `density` times the squared chord to the site.
+    ///
+    /// Each cell is fanned out from its site into triangles, and
+    /// `density` is sampled at the centroid of each triangle, in degrees.
+    /// With a constant density both are exact.
+    ///
+    /// A site without a cell, or whose cell has no weight, is its own
+    /// centroid.
+    pub(crate) fn centroids(
+        &self,
+        density: impl Fn(&Coord<T>) -> T,
+    ) -> (Vec<[T; 3]>, T) {
+        let two = T::one() + T::one();
+        let mut energy = T::zero();
+        let centroids = self
+            .vectors
+            .iter()
+            .enumerate()
+            .map(|(site, s)| {
+                let Some(ring) = self.polygons.get(site) else {
+                    return *s;
+                };
+                let mut total = [T::zero(); 3];
+                for (a, b) in ring_edges(ring) {
+                    let triangle = [
+                        *s,
+                        cartesian(&self.centers[a]),
+                        cartesian(&self.centers[b]),
+                    ];
+                    let area = area_cartesian(&triangle).abs();
+                    if area == T::zero() {
+                        continue;
+                    }
+                    let m = moment(&triangle);
+                    let rho = density(&spherical(&normalize(&m)));
+                    // |x - s|² = 2 - 2 x·s on the unit sphere.
+                    energy = energy + rho * two * (area - dot(&m, s));
+                    for (ti, mi) in total.iter_mut().zip(m) {
+                        *ti = *ti + rho * mi;
+                    }
+                }
+                let length = dot(&total, &total).sqrt();
+                if length > T::zero() && length.is_finite() {
+                    total.map(|x| x / length)
+                } else {
+                    *s
+                }
+            })
+            .collect();
+        (centroids, energy)
+    }
+}
@@ -6,27 +6,32 @@ use geo::CoordFloat;
 use geo_types::Coord;
 use num_traits::FloatConst;
 
-use super::cartesian::cartesian;
 use super::TriIndex;
 
 pub fn circumcenters<'a, T>(
     triangles: &'a [TriIndex],
-    points: &'a [Coord<T>],
+    vectors: &'a [[T; 3]],
 ) -> impl Iterator<Item = Coord<T>> + 'a
 where
     T: CoordFloat + FloatConst,
 {
     triangles.iter().map(|tri| {
-        let c = [
-            cartesian(&points[tri[0]]),
-            cartesian(&points[tri[1]]),
-            cartesian(&points[tri[2]]),
-        ];
-
-        let v: [T; 3] = add(
-            add(cross(&c[1], &c[0]), cross(&c[2], &c[1])),
-            cross(&c[0], &c[2]),
-        );
-        spherical(&normalize(&v))
+        spherical(&circumcenter(&[
+            vectors[tri[0]],
+            vectors[tri[1]],
+            vectors[tri[2]],
+        ]))
     })
 }
+
+/// The circumcenter of a triangle, as a unit vector.
+pub fn circumcenter<T>(c: &[[T; 3]; 3]) -> [T; 3]
+where
+    T: CoordFloat,
+{
+    let v: [T; 3] = add(
+        add(cross(&c[1], &c[0]), cross(&c[2], &c[1])),
+        cross(&c[0], &c[2]),
+    );
+    normalize(&v)
+}
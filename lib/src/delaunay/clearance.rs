@@ -0,0 +1,253 @@
+use core::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::collections::HashSet;
+
+use d3_geo_rs::cartesian::spherical;
+use geo::CoordFloat;
+use geo::LineString;
+use geo_types::Coord;
+use num_traits::FloatConst;
+
+use crate::queue::Queued;
+
+use super::cartesian::cartesian;
+use super::within::angle;
+use super::within::arc_distance;
+use super::within::closest;
+use super::Delaunay;
+
+/// A path along the edges of the cells, between two points snapped onto
+/// them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClearancePath<T>
+where
+    T: CoordFloat,
+{
+    /// The cell vertices visited, indices into `centers`, between the
+    /// snapped ends.
+    pub centers: Vec<usize>,
+    /// The length of the path.
+    pub length: T,
+    /// The least distance between the path and a site.
+    pub clearance: T,
+    /// The path, from the snapped start to the snapped end.
+    pub geometry: LineString<T>,
+}
+
+/// An arc of the graph of the cell edges.
+#[derive(Clone, Debug)]
+struct Arc<T> {
+    to: usize,
+    clearance: T,
+    length: T,
+}
+
+/// The graph of the cell edges, the nodes being the cell vertices and
+/// the two snapped ends, last.
+type Graph<T> = Vec<Vec<Arc<T>>>;
+
+/// The point of the boundary of a cell closest to a given point: the site
+/// of the cell, the point and the ends of the cell edge holding it.
+type Snap<T> = (usize, [T; 3], usize, usize);
+
+/// The edges of a ring, between distinct vertices.
+pub(super) fn ring_edges(
+    ring: &[usize],
+) -> impl Iterator<Item = (usize, usize)> + '_ {
+    (0..ring.len())
+        .map(|k| (ring[k], ring[(k + 1) % ring.len()]))
+        .filter(|(a, b)| a != b)
+}
+
+/// Dijkstra's algorithm, `cost` extending the cost of a path by an arc,
+/// None when the arc may not be followed.
+///
+/// Returns the nodes of the cheapest path and its cost.
+fn search<T: CoordFloat>(
+    graph: &Graph<T>,
+    from: usize,
+    to: usize,
+    start: T,
+    cost: impl Fn(T, &Arc<T>) -> Option<T>,
+) -> Option<(Vec<usize>, T)> {
+    let mut costs = vec![T::infinity(); graph.len()];
+    let mut previous = vec![None; graph.len()];
+    costs[from] = start;
+    let mut queue = BinaryHeap::from([Queued {
+        priority: start,
+        cost: start,
+        node: from,
+    }]);
+    while let Some(Queued { cost: c, node, .. }) = queue.pop() {
+        if node == to {
+            let mut nodes = vec![to];
+            while let Some(p) = previous[nodes[nodes.len() - 1]] {
+                nodes.push(p);
+            }
+            nodes.reverse();
+            return Some((nodes, c));
+        }
+        if c > costs[node] {
+            continue;
+        }
+        for arc in &graph[node] {
+            let Some(next) = cost(c, arc) else {
+                continue;
+            };
+            if next < costs[arc.to] {
+                costs[arc.to] = next;
+                previous[arc.to] = Some(node);
+                queue.push(Queued {
+                    priority: next,
+                    cost: next,
+                    node: arc.to,
+                });
+            }
+        }
+    }
+    None
+}
+
+impl<T> Delaunay<T>
+where
+    T: CoordFloat + FloatConst,
+{
+    /// Returns the path along the edges of the cells, from `from` to `to`
+    /// ( in degrees ) snapped onto the nearest cell edges, which stays as
+    /// far as possible from the sites.
+    ///
+    /// The path maximizes its clearance, the least distance ( in radians )
+    /// between the path and a site, and is the shortest with that
+    /// clearance.
+    ///
+    /// None when the cells were not computed, see [`super::Structures`].
+    #[must_use]
+    pub fn max_clearance_path(
+        &self,
+        from: &Coord<T>,
+        to: &Coord<T>,
+    ) -> Option<ClearancePath<T>> {
+        let (graph, snaps) = self.clearance_graph(from, to)?;
+        let (s, e) = (graph.len() - 2, graph.len() - 1);
+        let (_, bottleneck) =
+            search(&graph, s, e, -T::infinity(), |c, arc| {
+                Some(c.max(-arc.clearance))
+            })?;
+        self.shortest_clear_path(&graph, &snaps, -bottleneck)
+    }
+
+    /// Returns the shortest path along the edges of the cells, from `from`
+    /// to `to` ( in degrees ) snapped onto the nearest cell edges, which
+    /// stays at least `clearance` ( in radians ) away from the sites.
+    ///
+    /// None when there is no such path, or when the cells were not
+    /// computed, see [`super::Structures`].
+    #[must_use]
+    pub fn clearance_path(
+        &self,
+        from: &Coord<T>,
+        to: &Coord<T>,
+        clearance: T,
+    ) -> Option<ClearancePath<T>> {
+        let (graph, snaps) = self.clearance_graph(from, to)?;
+        self.shortest_clear_path(&graph, &snaps, clearance)
+    }
+
+    /// The shortest path between the snapped ends, following only the
+    /// arcs with at least `clearance`.
+    fn shortest_clear_path(
+        &self,
+        graph: &Graph<T>,
+        snaps: &[Snap<T>; 2],
+        clearance: T,
+    ) -> Option<ClearancePath<T>> {
+        let (s, e) = (graph.len() - 2, graph.len() - 1);
+        let (nodes, length) = search(graph, s, e, T::zero(), |c, arc| {
+            (arc.clearance >= clearance).then(|| c + arc.length)
+        })?;
+        let clearance = nodes
+            .windows(2)
+            .filter_map(|w| graph[w[0]].iter().find(|arc| arc.to == w[1]))
+            .map(|arc| arc.clearance)
+            .fold(T::infinity(), T::min);
+        let geometry = nodes
+            .iter()
+            .map(|&n| {
+                if n < s {
+                    self.centers[n]
+                } else {
+                    spherical(&snaps[n - s].1)
+                }
+            })
+            .collect();
+        Some(ClearancePath {
+            centers: nodes[1..nodes.len() - 1].to_vec(),
+            length,
+            clearance,
+            geometry,
+        })
+    }
+
+    /// The graph of the cell edges, with `from` and `to` snapped onto it.
+    fn clearance_graph(
+        &self,
+        from: &Coord<T>,
+        to: &Coord<T>,
+    ) -> Option<(Graph<T>, [Snap<T>; 2])> {
+        let vectors: Vec<[T; 3]> = self.centers.iter().map(cartesian).collect();
+        let n = vectors.len();
+        let mut graph: Graph<T> = vec![Vec::new(); n + 2];
+        let mut link = |a: usize, b: usize, clearance: T, length: T| {
+            graph[a].push(Arc {
+                to: b,
+                clearance,
+                length,
+            });
+            graph[b].push(Arc {
+                to: a,
+                clearance,
+                length,
+            });
+        };
+
+        let mut seen = HashSet::new();
+        for (site, ring) in self.polygons.iter().enumerate() {
+            for (a, b) in ring_edges(ring) {
+                if seen.insert((a.min(b), a.max(b))) {
+                    let (va, vb) = (&vectors[a], &vectors[b]);
+                    let clearance = arc_distance(&self.vectors[site], va, vb);
+                    link(a, b, clearance, angle(va, vb));
+                }
+            }
+        }
+
+        let snaps = [self.snap(from, &vectors)?, self.snap(to, &vectors)?];
+        for (k, &(site, q, a, b)) in snaps.iter().enumerate() {
+            let v = &self.vectors[site];
+            for c in [a, b] {
+                let clearance = arc_distance(v, &q, &vectors[c]);
+                link(n + k, c, clearance, angle(&q, &vectors[c]));
+            }
+        }
+        // Both ends on the same cell edge.
+        let [(site, qs, a, b), (_, qe, c, d)] = snaps;
+        if (a.min(b), a.max(b)) == (c.min(d), c.max(d)) {
+            let clearance = arc_distance(&self.vectors[site], &qs, &qe);
+            link(n, n + 1, clearance, angle(&qs, &qe));
+        }
+        Some((graph, snaps))
+    }
+
+    /// Snaps `p` ( in degrees ) onto the boundary of the cell holding it.
+    fn snap(&self, p: &Coord<T>, vectors: &[[T; 3]]) -> Option<Snap<T>> {
+        let site = self.find(p, None)?;
+        let xyz = cartesian(p);
+        ring_edges(self.polygons.get(site)?)
+            .map(|(a, b)| {
+                let q = closest(&xyz, &vectors[a], &vectors[b]);
+                (angle(&xyz, &q), q, a, b)
+            })
+            .min_by(|x, y| x.0.partial_cmp(&y.0).unwrap_or(Ordering::Equal))
+            .map(|(_, q, a, b)| (site, q, a, b))
+    }
+}
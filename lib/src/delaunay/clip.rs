@@ -0,0 +1,466 @@
+use core::cmp::Ordering;
+
+use d3_geo_rs::cartesian::cross;
+use d3_geo_rs::cartesian::dot;
+use d3_geo_rs::cartesian::normalize;
+use d3_geo_rs::cartesian::spherical;
+use geo::CoordFloat;
+use geo::LineString;
+use geo::MultiPolygon;
+use geo::Polygon;
+use num_traits::FloatConst;
+
+use super::cartesian::cartesian;
+use super::clearance::ring_edges;
+use super::excess::area_cartesian;
+use super::Delaunay;
+
+/// A ring, as unit vectors.
+type Ring<T> = Vec<[T; 3]>;
+
+/// A place on the boundary of a region: the index of a side, and the
+/// angle from the first corner of that side.
+type Position<T> = (usize, T);
+
+fn compare<T: CoordFloat>(a: &Position<T>, b: &Position<T>) -> Ordering {
+    a.0.cmp(&b.0)
+        .then(a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+}
+
+/// The area of a ring seen from `c`, in steradians, positive when it
+/// winds clockwise seen from outside, as the cells.
+///
+/// Exact when `-c` is outside the ring, otherwise less by `4 * PI`.
+fn signed_area<T: CoordFloat>(ring: &[[T; 3]], c: &[T; 3]) -> T {
+    ring_edges_of(ring)
+        .map(|(p, q)| area_cartesian(&[*c, *p, *q]))
+        .fold(T::zero(), |acc, x| acc + x)
+}
+
+/// Whether `x` is on the right of `ring`.
+fn contains<T: CoordFloat>(ring: &[[T; 3]], x: &[T; 3]) -> bool {
+    signed_area(ring, &x.map(|c| -c)) < T::zero()
+}
+
+/// The angle from `a` to `x` about the axis `n`, in `[0, 2 * PI)`.
+fn angle<T: CoordFloat + FloatConst>(a: &[T; 3], x: &[T; 3], n: &[T; 3]) -> T {
+    let t = dot(&cross(a, x), n).atan2(dot(a, x));
+    if t < T::zero() {
+        t + T::PI() + T::PI()
+    } else {
+        t
+    }
+}
+
+/// The edges of a ring of unit vectors.
+fn ring_edges_of<T>(ring: &[T]) -> impl Iterator<Item = (&T, &T)> {
+    (0..ring.len()).map(|k| (&ring[k], &ring[(k + 1) % ring.len()]))
+}
+
+/// A mask polygon, its rings as unit vectors.
+struct Mask<T> {
+    exterior: Ring<T>,
+    holes: Vec<Ring<T>>,
+}
+
+impl<T: CoordFloat> Mask<T> {
+    /// The rings, each with the inside of the mask on its right.
+    fn rings(&self) -> impl Iterator<Item = &Ring<T>> {
+        core::iter::once(&self.exterior).chain(&self.holes)
+    }
+
+    fn contains(&self, x: &[T; 3]) -> bool {
+        self.rings().all(|ring| contains(ring, x))
+    }
+}
+
+/// The unit vectors of a ring, without the closing vertex.
+fn ring_of<T: CoordFloat>(ls: &LineString<T>) -> Ring<T> {
+    let mut ring: Ring<T> = ls.0.iter().map(cartesian).collect();
+    if ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    ring
+}
+
+/// Where a mask ring meets a region.
+enum Event<T> {
+    /// A vertex inside the region.
+    Vertex([T; 3]),
+    /// Where the ring enters the region.
+    Enter([T; 3], Position<T>),
+    /// Where the ring leaves the region.
+    Leave([T; 3], Position<T>),
+}
+
+/// A part of a mask ring inside a region.
+struct Chain<T> {
+    points: Ring<T>,
+    enter: Position<T>,
+    leave: Position<T>,
+}
+
+/// A convex cell, clockwise seen from outside, its sides the great circle
+/// arcs joining its corners.
+struct Region<T> {
+    /// The vertices, starting at a corner.
+    vertices: Ring<T>,
+    /// The position of each vertex.
+    positions: Vec<Position<T>>,
+    /// The first corner of each side, and the normal of the great circle
+    /// of the side, pointing outside.
+    sides: Vec<([T; 3], [T; 3])>,
+    /// The site, inside the region.
+    site: [T; 3],
+}
+
+impl<T> Region<T>
+where
+    T: CoordFloat + FloatConst,
+{
+    /// None when the cell has fewer than three distinct vertices.
+    fn new(cell: &[[T; 3]], site: &[T; 3]) -> Option<Self> {
+        let epsilon = T::epsilon() * T::from(64_f64).unwrap();
+        let distinct = |a: &[T; 3], b: &[T; 3]| {
+            let d = [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+            dot(&d, &d) > epsilon
+        };
+        let mut vertices: Ring<T> = Vec::with_capacity(cell.len());
+        for v in cell {
+            if vertices.last().is_none_or(|p| distinct(p, v)) {
+                vertices.push(*v);
+            }
+        }
+        while vertices.len() > 1
+            && !distinct(&vertices[0], &vertices[vertices.len() - 1])
+        {
+            vertices.pop();
+        }
+        if vertices.len() < 3 {
+            return None;
+        }
+        if signed_area(&vertices, site) < T::zero() {
+            vertices.reverse();
+        }
+
+        // Consecutive sides along one great circle are merged.
+        let n = vertices.len();
+        let mut normals: Ring<T> = ring_edges_of(&vertices)
+            .map(|(a, b)| normalize(&cross(a, b)))
+            .collect();
+        let corner = |k: usize, normals: &Ring<T>| {
+            dot(&normals[(k + n - 1) % n], &normals[k]) < T::one() - epsilon
+        };
+        let first = (0..n).find(|&k| corner(k, &normals)).unwrap_or(0);
+        vertices.rotate_left(first);
+        normals.rotate_left(first);
+
+        let mut sides = Vec::new();
+        let mut positions = Vec::with_capacity(n);
+        for k in 0..n {
+            if k == 0 || corner(k, &normals) {
+                sides.push((vertices[k], normals[k]));
+            }
+            let (a, normal) = &sides[sides.len() - 1];
+            positions.push((sides.len() - 1, angle(a, &vertices[k], normal)));
+        }
+        Some(Self {
+            vertices,
+            positions,
+            sides,
+            site: *site,
+        })
+    }
+
+    /// The area of a ring inside the region, in steradians, positive when
+    /// it winds clockwise seen from outside.
+    fn area(&self, ring: &[[T; 3]]) -> T {
+        // The region is convex, the antipode of its site outside it.
+        signed_area(ring, &self.site)
+    }
+
+    /// The chord parameters at which the arc from `p` to `q` enters and
+    /// leaves the region, with the side crossed, as in the Cyrus–Beck
+    /// algorithm. None when the arc stays outside.
+    #[allow(clippy::type_complexity)]
+    fn arc(
+        &self,
+        p: &[T; 3],
+        q: &[T; 3],
+    ) -> Option<((T, Option<usize>), (T, Option<usize>))> {
+        let mut enter = (T::zero(), None);
+        let mut leave = (T::one(), None);
+        for (i, (_, normal)) in self.sides.iter().enumerate() {
+            let (dp, dq) = (-dot(p, normal), -dot(q, normal));
+            if dp < T::zero() && dq < T::zero() {
+                return None;
+            }
+            if dp < T::zero() {
+                let t = dp / (dp - dq);
+                if t > enter.0 {
+                    enter = (t, Some(i));
+                }
+            } else if dq < T::zero() {
+                let t = dp / (dp - dq);
+                if t < leave.0 {
+                    leave = (t, Some(i));
+                }
+            }
+        }
+        (enter.0 <= leave.0).then_some((enter, leave))
+    }
+
+    /// Splits a mask ring into the chains inside the region, or adds it to
+    /// `loops` when it lies inside.
+    fn trace(
+        &self,
+        ring: &[[T; 3]],
+        loops: &mut Vec<Ring<T>>,
+        chains: &mut Vec<Chain<T>>,
+    ) {
+        let at = |p: &[T; 3], q: &[T; 3], t: T| {
+            if t == T::zero() {
+                *p
+            } else if t == T::one() {
+                *q
+            } else {
+                normalize(&[
+                    p[0] + t * (q[0] - p[0]),
+                    p[1] + t * (q[1] - p[1]),
+                    p[2] + t * (q[2] - p[2]),
+                ])
+            }
+        };
+        let position = |x: &[T; 3], side: usize| {
+            let (a, normal) = &self.sides[side];
+            (side, angle(a, x, normal))
+        };
+        let mut events = Vec::new();
+        for (p, q) in ring_edges_of(ring).filter(|(p, q)| p != q) {
+            let Some(((t0, s0), (t1, s1))) = self.arc(p, q) else {
+                continue;
+            };
+            if let Some(side) = s0 {
+                let x = at(p, q, t0);
+                events.push(Event::Enter(x, position(&x, side)));
+            }
+            match s1 {
+                Some(side) => {
+                    let x = at(p, q, t1);
+                    events.push(Event::Leave(x, position(&x, side)));
+                }
+                None => events.push(Event::Vertex(*q)),
+            }
+        }
+
+        let Some(start) =
+            events.iter().position(|e| matches!(e, Event::Enter(..)))
+        else {
+            // Wholly inside, or wholly outside.
+            if !events.is_empty() {
+                loops.push(
+                    events
+                        .iter()
+                        .filter_map(|e| match e {
+                            Event::Vertex(x) => Some(*x),
+                            _ => None,
+                        })
+                        .collect(),
+                );
+            }
+            return;
+        };
+        events.rotate_left(start);
+        let mut chain: Option<Chain<T>> = None;
+        for event in events {
+            match event {
+                Event::Enter(x, enter) => {
+                    chain = Some(Chain {
+                        points: vec![x],
+                        enter,
+                        leave: enter,
+                    });
+                }
+                Event::Vertex(x) => {
+                    if let Some(chain) = chain.as_mut() {
+                        chain.points.push(x);
+                    }
+                }
+                Event::Leave(x, leave) => {
+                    if let Some(mut chain) = chain.take() {
+                        chain.points.push(x);
+                        chain.leave = leave;
+                        chains.push(chain);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Pushes the vertices of the region strictly between two positions,
+    /// walking along its boundary.
+    fn walk(&self, from: &Position<T>, to: &Position<T>, ring: &mut Ring<T>) {
+        let after = |p: &Position<T>| compare(p, from) == Ordering::Greater;
+        let before = |p: &Position<T>| compare(p, to) == Ordering::Less;
+        let vertices = self.vertices.iter().zip(&self.positions);
+        if compare(to, from) == Ordering::Less {
+            ring.extend(
+                vertices.clone().filter(|(_, p)| after(p)).map(|(v, _)| *v),
+            );
+            ring.extend(vertices.filter(|(_, p)| before(p)).map(|(v, _)| *v));
+        } else {
+            ring.extend(
+                vertices
+                    .filter(|(_, p)| after(p) && before(p))
+                    .map(|(v, _)| *v),
+            );
+        }
+    }
+
+    /// Joins the chains into rings, along the boundary of the region.
+    ///
+    /// The inside of the mask is on the right of the chains, as the inside
+    /// of the region on the right of its boundary: leaving the region, the
+    /// ring follows the boundary forward to the next chain entering it.
+    fn join(&self, chains: &[Chain<T>], loops: &mut Vec<Ring<T>>) {
+        let mut order: Vec<usize> = (0..chains.len()).collect();
+        order.sort_by(|&a, &b| compare(&chains[a].enter, &chains[b].enter));
+        let mut used = vec![false; chains.len()];
+        for &first in &order {
+            let mut ring = Vec::new();
+            let mut i = first;
+            while !used[i] {
+                used[i] = true;
+                ring.extend_from_slice(&chains[i].points);
+                let leave = &chains[i].leave;
+                let next = order
+                    .iter()
+                    .copied()
+                    .find(|&j| {
+                        compare(&chains[j].enter, leave) != Ordering::Less
+                    })
+                    .unwrap_or(order[0]);
+                self.walk(leave, &chains[next].enter, &mut ring);
+                i = next;
+            }
+            if !ring.is_empty() {
+                loops.push(ring);
+            }
+        }
+    }
+
+    /// The part of the region inside the mask.
+    fn clip(&self, masks: &[Mask<T>]) -> Vec<Polygon<T>> {
+        let mut loops = Vec::new();
+        let mut chains = Vec::new();
+        for ring in masks.iter().flat_map(Mask::rings) {
+            self.trace(ring, &mut loops, &mut chains);
+        }
+        if chains.is_empty() {
+            // The boundary is wholly inside or outside the mask.
+            let [a, b] = [&self.vertices[0], &self.vertices[1]];
+            let probe = normalize(&[a[0] + b[0], a[1] + b[1], a[2] + b[2]]);
+            if masks.iter().any(|mask| mask.contains(&probe)) {
+                loops.push(self.vertices.clone());
+            }
+        } else {
+            self.join(&chains, &mut loops);
+        }
+        self.polygons(loops)
+    }
+
+    /// Sorts rings inside the region into exteriors, clockwise, and holes,
+    /// each hole going to the smallest exterior around it.
+    fn polygons(&self, loops: Vec<Ring<T>>) -> Vec<Polygon<T>> {
+        let tolerance = T::epsilon().sqrt() * self.area(&self.vertices);
+        let mut exteriors = Vec::new();
+        let mut holes = Vec::new();
+        for mut ring in loops {
+            ring.dedup();
+            while ring.len() > 1 && ring.first() == ring.last() {
+                ring.pop();
+            }
+            if ring.len() < 3 {
+                continue;
+            }
+            let area = self.area(&ring);
+            if area > T::zero() {
+                exteriors.push((ring, area, Vec::new()));
+            } else {
+                holes.push((ring, area));
+            }
+        }
+        for (hole, area) in holes {
+            let around = exteriors
+                .iter_mut()
+                .filter(|(exterior, _, _)| {
+                    let inside =
+                        hole.iter().filter(|v| contains(exterior, v)).count();
+                    2 * inside > hole.len()
+                })
+                .min_by(|a, b| {
+                    a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal)
+                });
+            if let Some((_, remaining, interiors)) = around {
+                *remaining = *remaining + area;
+                interiors.push(hole);
+            }
+        }
+        let line = |ring: &Ring<T>| -> LineString<T> {
+            ring.iter().map(spherical).collect()
+        };
+        exteriors
+            .into_iter()
+            .filter(|(_, remaining, _)| *remaining > tolerance)
+            .map(|(exterior, _, holes)| {
+                Polygon::new(line(&exterior), holes.iter().map(line).collect())
+            })
+            .collect()
+    }
+}
+
+impl<T> Delaunay<T>
+where
+    T: CoordFloat + FloatConst,
+{
+    /// Returns the part of the cell of each site inside `mask` ( in
+    /// degrees ), in site order.
+    ///
+    /// The edges of the mask are great circle arcs, and the inside of a
+    /// ring is on its right, as for the cells: exterior rings wind
+    /// clockwise and holes counterclockwise, seen from outside the
+    /// sphere. An exterior ring may hold a pole, or more than a
+    /// hemisphere. The rings of the mask may not cross.
+    ///
+    /// Each part of a cell is a polygon of its own, so that a concave mask
+    /// may split a cell. A cell outside the mask gives an empty
+    /// `MultiPolygon`. Empty when the cells were not computed, see
+    /// [`super::Structures`].
+    #[must_use]
+    pub fn polygons_clipped(
+        &self,
+        mask: &MultiPolygon<T>,
+    ) -> Vec<MultiPolygon<T>> {
+        let masks: Vec<Mask<T>> = mask
+            .0
+            .iter()
+            .map(|p| Mask {
+                exterior: ring_of(p.exterior()),
+                holes: p.interiors().iter().map(ring_of).collect(),
+            })
+            .filter(|m| m.exterior.len() >= 3)
+            .collect();
+        self.polygons
+            .iter()
+            .enumerate()
+            .map(|(site, ring)| {
+                let cell: Ring<T> = ring_edges(ring)
+                    .map(|(a, _)| cartesian(&self.centers[a]))
+                    .collect();
+                Region::new(&cell, &self.vectors[site])
+                    .map_or_else(Vec::new, |region| region.clip(&masks))
+                    .into()
+            })
+            .collect()
+    }
+}
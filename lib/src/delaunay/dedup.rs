@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use geo::CoordFloat;
+use geo_types::Coord;
+use num_traits::FloatConst;
+
+use super::cartesian::cartesian;
+
+type Cell = (i64, i64, i64);
+
+/// Merges points which are within `tolerance` ( an angle in radians ) of
+/// an earlier point.
+///
+/// Returns the remaining sites, in order of first occurrence, and for each
+/// input point the index of the site it was merged into.
+///
+/// With a zero tolerance only points with identical coordinates are merged.
+/// Points with a non-finite coordinate are never merged.
+///
+/// # Panics
+///  Will never happen as constants will always be converted into T.
+#[must_use]
+pub fn dedup<T>(
+    points: &[Coord<T>],
+    tolerance: T,
+) -> (Vec<Coord<T>>, Vec<usize>)
+where
+    T: CoordFloat + FloatConst,
+{
+    let vectors: Vec<[T; 3]> = points.iter().map(cartesian).collect();
+    let (sites, index) = dedup_vectors(points, &vectors, tolerance);
+    (sites.into_iter().map(|i| points[i]).collect(), index)
+}
+
+/// As [`dedup`], with the unit vector of each point supplied.
+///
+/// Returns the index of the input point representing each site.
+pub(super) fn dedup_vectors<T>(
+    points: &[Coord<T>],
+    vectors: &[[T; 3]],
+    tolerance: T,
+) -> (Vec<usize>, Vec<usize>)
+where
+    T: CoordFloat + FloatConst,
+{
+    let mut sites: Vec<usize> = Vec::with_capacity(points.len());
+    let mut index: Vec<usize> = Vec::with_capacity(points.len());
+
+    if tolerance <= T::zero() {
+        let mut seen: HashMap<[(u64, i16, i8); 2], usize> =
+            HashMap::with_capacity(points.len());
+        for (i, p) in points.iter().enumerate() {
+            if !(p.x + p.y).is_finite() {
+                index.push(sites.len());
+                sites.push(i);
+                continue;
+            }
+            // Adding zero folds -0 into 0.
+            let key = [
+                (p.x + T::zero()).integer_decode(),
+                (p.y + T::zero()).integer_decode(),
+            ];
+            let site = *seen.entry(key).or_insert_with(|| {
+                sites.push(i);
+                sites.len() - 1
+            });
+            index.push(site);
+        }
+        return (sites, index);
+    }
+
+    // Bucket the unit vectors in a grid of cubes whose side is the chord
+    // subtended by the tolerance, a match is then in a neighboring cube.
+    let two = T::from(2_f64).unwrap();
+    let chord = two * (tolerance.min(T::PI()) / two).sin();
+    let chord2 = chord * chord;
+    let cell_of = |v: &[T; 3]| -> Cell {
+        let c = |x: T| (x / chord).floor().to_i64().unwrap_or(0);
+        (c(v[0]), c(v[1]), c(v[2]))
+    };
+
+    let mut grid: HashMap<Cell, Vec<(usize, [T; 3])>> = HashMap::new();
+    for (i, (p, v)) in points.iter().zip(vectors).enumerate() {
+        if !(p.x + p.y).is_finite() {
+            index.push(sites.len());
+            sites.push(i);
+            continue;
+        }
+        let (ci, cj, ck) = cell_of(v);
+        let mut found = None;
+        'search: for di in -1..=1 {
+            for dj in -1..=1 {
+                for dk in -1..=1 {
+                    let Some(bucket) = grid.get(&(ci + di, cj + dj, ck + dk))
+                    else {
+                        continue;
+                    };
+                    for (site, w) in bucket {
+                        let d = [v[0] - w[0], v[1] - w[1], v[2] - w[2]];
+                        if d[0] * d[0] + d[1] * d[1] + d[2] * d[2] <= chord2 {
+                            found = Some(*site);
+                            break 'search;
+                        }
+                    }
+                }
+            }
+        }
+        let site = found.unwrap_or_else(|| {
+            sites.push(i);
+            let site = sites.len() - 1;
+            grid.entry((ci, cj, ck)).or_default().push((site, *v));
+            site
+        });
+        index.push(site);
+    }
+    (sites, index)
+}
+
+#[cfg(test)]
+mod dedup_test {
+    extern crate pretty_assertions;
+
+    use geo_types::Coord;
+    use pretty_assertions::assert_eq;
+
+    use super::dedup;
+
+    #[test]
+    fn merges_identical_points() {
+        let points = vec![
+            Coord { x: 0f64, y: 0f64 },
+            Coord { x: 10f64, y: 0f64 },
+            Coord { x: -0f64, y: 0f64 },
+            Coord { x: 10f64, y: 0f64 },
+            Coord { x: 10f64, y: 1e-9 },
+        ];
+        let (sites, index) = dedup(&points, 0f64);
+        assert_eq!(sites.len(), 3);
+        assert_eq!(index, vec![0, 1, 0, 1, 2]);
+    }
+
+    #[test]
+    fn merges_points_within_the_tolerance() {
+        let points = vec![
+            Coord { x: 0f64, y: 0f64 },
+            Coord { x: 10f64, y: 0f64 },
+            Coord { x: 0.5f64, y: 0f64 },
+            Coord {
+                x: 10f64,
+                y: 89.9f64,
+            },
+            Coord {
+                x: -170f64,
+                y: 89.9f64,
+            },
+            Coord { x: 2f64, y: 0f64 },
+        ];
+        let (sites, index) = dedup(&points, 1f64.to_radians());
+        assert_eq!(sites.len(), 4);
+        assert_eq!(index, vec![0, 1, 0, 2, 2, 3]);
+    }
+
+    #[test]
+    fn keeps_non_finite_points() {
+        let points = vec![
+            Coord {
+                x: f64::NAN,
+                y: 0f64,
+            },
+            Coord {
+                x: f64::NAN,
+                y: 0f64,
+            },
+        ];
+        let (sites, index) = dedup(&points, 1f64);
+        assert_eq!(sites.len(), 2);
+        assert_eq!(index, vec![0, 1]);
+    }
+}
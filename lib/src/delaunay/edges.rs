@@ -1,19 +1,18 @@
 use std::collections::HashSet;
 
 use geo::CoordFloat;
-use geo_types::Coord;
 
 use crate::extent::extent;
 
-use super::excess::excess;
+use super::excess::excess_cartesian;
 use super::EdgeIndex;
 use super::TriIndex;
 
 pub fn edges<T: CoordFloat>(
     triangles: &[TriIndex],
-    point: &[Coord<T>],
+    vectors: &[[T; 3]],
 ) -> HashSet<EdgeIndex> {
-    if point.len() == 1 {
+    if vectors.len() == 1 {
         return HashSet::from([(0usize, 1usize)]);
     }
     // capacity is a underestimate but if triangles is large
@@ -25,8 +24,8 @@ pub fn edges<T: CoordFloat>(
             continue;
         }
 
-        let ex_in = [point[tri[0]], point[tri[1]], point[tri[2]]];
-        if excess(&ex_in) < zero {
+        let ex_in = [vectors[tri[0]], vectors[tri[1]], vectors[tri[2]]];
+        if excess_cartesian(&ex_in) < zero {
             continue;
         }
 
@@ -8,10 +8,24 @@ use super::cartesian::cartesian;
 
 /// Spherical excess of a triangle (in spherical coordinates).
 pub fn excess<T: CoordFloat>(triangle_p: &[Coord<T>; 3]) -> T {
-    let triangle = [
+    excess_cartesian(&[
         cartesian(&triangle_p[0]),
         cartesian(&triangle_p[1]),
         cartesian(&triangle_p[2]),
-    ];
+    ])
+}
+
+/// Spherical excess of a triangle (as unit vectors).
+pub(crate) fn excess_cartesian<T: CoordFloat>(triangle: &[[T; 3]; 3]) -> T {
     dot(&triangle[0], &cross(&triangle[2], &triangle[1]))
 }
+
+/// Area of a triangle (as unit vectors), positive when oriented as the
+/// triangles of a [`super::Delaunay`].
+pub(crate) fn area_cartesian<T: CoordFloat>(triangle: &[[T; 3]; 3]) -> T {
+    let [a, b, c] = triangle;
+    let one = T::one();
+    (one + one)
+        * excess_cartesian(triangle)
+            .atan2(one + dot(a, b) + dot(b, c) + dot(c, a))
+}
@@ -1,3 +1,6 @@
+use core::cmp::Ordering;
+use std::collections::HashSet;
+
 use geo::CoordFloat;
 use geo_types::Coord;
 use num_traits::Float;
@@ -18,36 +21,128 @@ impl<T> Delaunay<T>
 where
     T: CoordFloat,
 {
-    #[allow(clippy::similar_names)]
-    pub(crate) fn find(
-        &self,
-        p: &Coord<T>,
-        next_p: Option<usize>,
-    ) -> Option<usize> {
-        let next_or_none = next_p.map_or(Some(0usize), Some);
-        let mut found = next_or_none;
+    /// Returns the site nearest to `p`, in degrees.
+    ///
+    /// The search walks the neighbors graph from the site `hint`, or from
+    /// the first site when `hint` is not a site. Starting close to `p`,
+    /// from the result of a nearby query, shortens the walk.
+    ///
+    /// Returns None when there are no sites or `p` is not finite.
+    #[must_use]
+    pub fn find(&self, p: &Coord<T>, hint: Option<usize>) -> Option<usize> {
+        if !(p.x + p.y).is_finite() {
+            return None;
+        }
+        let is_site =
+            |i: &usize| self.vectors.get(*i).is_some_and(|v| v[0].is_finite());
+        let start = hint
+            .filter(is_site)
+            .or_else(|| (0..self.vectors.len()).find(is_site))?;
+        Some(self.nearest(&cartesian(p), start))
+    }
+
+    /// Returns the site nearest to each point, as [`Delaunay::find`].
+    ///
+    /// The points are visited in bands of latitude, alternating east and
+    /// west, each walk starting from the previous result, so that the
+    /// walks stay short whatever the order of the points.
+    #[must_use]
+    pub fn find_many(&self, points: &[Coord<T>]) -> Vec<Option<usize>> {
+        // Bands of equal area, about one cell wide.
+        let bands = T::from(self.vectors.len()).unwrap_or_else(T::one).sqrt();
+        let two = T::one() + T::one();
+        let band = |p: &Coord<T>| {
+            ((p.y.to_radians().sin() + T::one()) / two * bands)
+                .floor()
+                .to_i64()
+                .unwrap_or(0)
+        };
+        let mut order: Vec<(i64, T, usize)> = points
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| (p.x + p.y).is_finite())
+            .map(|(i, p)| {
+                let b = band(p);
+                (b, if b % 2 == 0 { p.x } else { -p.x }, i)
+            })
+            .collect();
+        order.sort_unstable_by(|a, b| {
+            a.0.cmp(&b.0)
+                .then(a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+        });
+
+        let mut found = vec![None; points.len()];
+        let mut hint = None;
+        for (_, _, i) in order {
+            found[i] = self.find(&points[i], hint);
+            hint = found[i].or(hint);
+        }
+        found
+    }
+
+    /// Returns the `k` sites nearest to `p`, in degrees, with their
+    /// distance to `p` in radians, nearest first.
+    ///
+    /// The search grows out from the nearest site over the neighbors
+    /// graph, as on a Delaunay graph the `k` nearest sites are connected.
+    /// Returns fewer sites when there are fewer than `k`.
+    #[must_use]
+    pub fn k_nearest(&self, p: &Coord<T>, k: usize) -> Vec<(usize, T)> {
+        let mut nearest = Vec::with_capacity(k);
+        if k == 0 {
+            return nearest;
+        }
+        let Some(start) = self.find(p, None) else {
+            return nearest;
+        };
         let xyz = cartesian(p);
-        'outer: loop {
-            let cell = next_or_none.unwrap();
-            let mut next_or_no = None;
-            let mut dist =
-                distance2(&xyz, &cartesian(&self.delaunay.points[cell]));
+        let two = T::one() + T::one();
+        let mut seen = HashSet::from([start]);
+        let mut frontier = vec![(distance2(&xyz, &self.vectors[start]), start)];
+        while nearest.len() < k && !frontier.is_empty() {
+            let closest = (0..frontier.len())
+                .min_by(|&i, &j| {
+                    frontier[i]
+                        .0
+                        .partial_cmp(&frontier[j].0)
+                        .unwrap_or(Ordering::Equal)
+                })
+                .unwrap_or(0);
+            let (d2, site) = frontier.swap_remove(closest);
+            // From the chord to the arc.
+            nearest.push((site, two * (d2.sqrt() / two).min(T::one()).asin()));
+            for &i in self.neighbors.get(&site).into_iter().flatten() {
+                if seen.insert(i) {
+                    frontier.push((distance2(&xyz, &self.vectors[i]), i));
+                }
+            }
+        }
+        nearest
+    }
+
+    /// Walks the neighbors graph from `start` to the site nearest to the
+    /// unit vector `xyz`.
+    ///
+    /// The walk descends the distance, so it terminates, and on a
+    /// Delaunay graph it ends at the nearest site.
+    pub(super) fn nearest(&self, xyz: &[T; 3], start: usize) -> usize {
+        let mut cell = start;
+        let mut dist = distance2(xyz, &self.vectors[cell]);
+        loop {
+            let mut next = None;
             if let Some(row) = self.neighbors.get(&cell) {
                 for i in row {
-                    let ndist =
-                        distance2(&xyz, &cartesian(&self.delaunay.points[*i]));
+                    let ndist = distance2(xyz, &self.vectors[*i]);
                     if ndist < dist {
                         dist = ndist;
-                        next_or_no = Some(*i);
-                        found = Some(*i);
+                        next = Some(*i);
                     }
                 }
-
-                if next_or_no.is_some() {
-                    break 'outer;
-                }
+            }
+            match next {
+                Some(n) => cell = n,
+                None => return cell,
             }
         }
-        found
     }
 }
@@ -9,6 +9,7 @@ use num_traits::float::FloatConst;
 use num_traits::FromPrimitive;
 
 use d3_delaunay_rs::delaunay::Delaunay;
+use d3_geo_rs::cartesian::dot;
 use d3_geo_rs::projection::stereographic::Stereographic;
 use d3_geo_rs::projection::Build;
 use d3_geo_rs::projection::RawBase as ProjectionRawBase;
@@ -19,18 +20,75 @@ use d3_geo_rs::rot::rotation::Rotation;
 use d3_geo_rs::stream::DrainStub;
 use d3_geo_rs::Transform;
 
+use crate::error::ConstructionError;
+
+use super::cartesian::cartesian;
+
+/// Rejects sets of points which cannot be triangulated.
+fn check<T>(
+    points: &[Coord<T>],
+    vectors: &[[T; 3]],
+) -> Result<(), ConstructionError>
+where
+    T: CoordFloat,
+{
+    let points: Vec<[T; 3]> = points
+        .iter()
+        .zip(vectors)
+        .filter(|(p, _)| (p.x + p.y).is_finite())
+        .map(|(_, v)| *v)
+        .collect();
+    if points.len() < 2 {
+        return Err(ConstructionError::TooFewSites {
+            got: points.len(),
+            needed: 2,
+        });
+    }
+
+    let epsilon = T::epsilon() * T::from(64_f64).unwrap();
+    let distinct = |a: &[T; 3], b: &[T; 3]| {
+        let d = [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+        dot(&d, &d) > epsilon
+    };
+
+    let a = &points[0];
+    if !points.iter().any(|p| distinct(a, p)) {
+        return Err(ConstructionError::AllSitesCoincident);
+    }
+    Ok(())
+}
+
 /// Creates a delaunay object from a set of points.
 ///
+/// # Errors
+///  When fewer than two of the points are finite, or when all the finite
+///  points are at the same location. Any other set of points is
+///  triangulated.
+///
 /// # Panics
 ///  Will never happen as constants will always be converted into T.
-#[must_use]
-pub fn from_points<T>(points: &[Coord<T>]) -> Option<Delaunay<T>>
+pub fn from_points<T>(
+    points: &[Coord<T>],
+) -> Result<Delaunay<T>, ConstructionError>
 where
     T: 'static + CoordFloat + Default + FloatConst + FromPrimitive,
 {
-    if points.len() < 2 {
-        return None;
+    let vectors: Vec<[T; 3]> = points.iter().map(cartesian).collect();
+    from_sites(points, &vectors)
+}
+
+/// As [`from_points`], with the unit vector of each point supplied.
+pub(super) fn from_sites<T>(
+    points: &[Coord<T>],
+    vectors: &[[T; 3]],
+) -> Result<Delaunay<T>, ConstructionError>
+where
+    T: 'static + CoordFloat + Default + FloatConst + FromPrimitive,
+{
+    if !points.is_empty() && !points.iter().any(|p| (p.x + p.y).is_finite()) {
+        return Err(ConstructionError::AllSitesNonFinite);
     }
+    check(points, vectors)?;
 
     // Find a valid Pivot point to send to infinity.
     // The index of the first acceptable point in
@@ -94,6 +152,11 @@ where
     let mut delaunay = Delaunay::new(&points);
 
     // Clean up the triangulation.
+    //
+    // The pivot, sent to ( far, 0 ), and the three horizon points are the
+    // hull of the projected points, every other point lying well inside.
+    // So the triangulation is never empty, and each triangle on the hull
+    // has a single hull edge: `a` and `b` below are never EMPTY.
     let mut degenerate: Vec<usize> = Vec::new();
     let mut i: usize = 0;
     let l = delaunay.delaunator.halfedges.len();
@@ -132,5 +195,5 @@ where
     }
     // // there should always be 4 degenerate triangles
     debug_assert_eq!(degenerate.len(), 4);
-    Some(delaunay)
+    Ok(delaunay)
 }
@@ -3,13 +3,12 @@ use std::collections::HashSet;
 
 use delaunator::EMPTY;
 use geo::CoordFloat;
-use geo_types::Coord;
 
-use super::excess::excess;
+use super::excess::excess_cartesian;
 use super::EdgeIndex;
 use super::TriIndex;
 
-pub fn hull<T>(triangles: &[TriIndex], points: &[Coord<T>]) -> Vec<usize>
+pub fn hull<T>(triangles: &[TriIndex], vectors: &[[T; 3]]) -> Vec<usize>
 where
     T: CoordFloat,
 {
@@ -17,15 +16,15 @@ where
     let mut hull = Vec::new();
 
     for tri in triangles {
-        let ex_in: Vec<Coord<T>> = tri
+        let ex_in: Vec<[T; 3]> = tri
             .iter()
             .map(|i: &usize| {
-                let index: usize = if i > &points.len() { 0 } else { *i };
-                points[index]
+                let index: usize = if i > &vectors.len() { 0 } else { *i };
+                vectors[index]
             })
             .collect();
 
-        if excess(&[ex_in[0], ex_in[1], ex_in[2]]) < T::zero() {
+        if excess_cartesian(&[ex_in[0], ex_in[1], ex_in[2]]) < T::zero() {
             continue;
         }
 
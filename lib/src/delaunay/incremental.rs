@@ -0,0 +1,626 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use d3_geo_rs::cartesian::dot;
+use d3_geo_rs::cartesian::spherical;
+use geo::CoordFloat;
+use geo_types::Coord;
+use num_traits::FloatConst;
+use num_traits::FromPrimitive;
+
+use crate::error::ConstructionError;
+use crate::error::UpdateError;
+use crate::extent::extent;
+
+use super::cartesian::cartesian;
+use super::circumcenters::circumcenter;
+use super::excess::excess_cartesian;
+use super::urquhart::urquhart;
+use super::Delaunay;
+use super::EdgeIndex;
+use super::TriIndex;
+
+/// Reports the cells changed by an incremental update.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Update {
+    /// The site inserted or removed, or the site an inserted point was
+    /// merged into.
+    pub site: usize,
+    /// The sites whose cell changed, in increasing order.
+    pub changed: Vec<usize>,
+    /// The triangulation was rebuilt rather than updated locally.
+    pub rebuilt: bool,
+}
+
+/// The cell around a site: its triangles in ring order and its
+/// neighbors.
+type Ring = (Vec<usize>, Vec<usize>);
+
+fn is_finite<T: CoordFloat>(p: &Coord<T>) -> bool {
+    (p.x + p.y).is_finite()
+}
+
+impl<T> Delaunay<T>
+where
+    T: CoordFloat,
+{
+    /// The triangles around a site, without repeating the first one to
+    /// close the ring.
+    pub(crate) fn cell(&self, site: usize) -> &[usize] {
+        let polygon = self.polygons.get(site).map_or(&[][..], Vec::as_slice);
+        match polygon.split_last() {
+            Some((last, ring)) if ring.first() == Some(last) => ring,
+            _ => polygon,
+        }
+    }
+
+    /// The triangle other than `t` sharing the edge from `a` to `b`.
+    pub(super) fn adjacent(
+        &self,
+        t: usize,
+        a: usize,
+        b: usize,
+    ) -> Option<usize> {
+        // Cells on the hull also hold centers which are not triangles.
+        self.cell(a).iter().copied().find(|&u| {
+            u != t && self.triangles.get(u).is_some_and(|tri| tri.contains(&b))
+        })
+    }
+}
+
+impl<T> Delaunay<T>
+where
+    T: 'static + CoordFloat + Default + FloatConst + FromPrimitive,
+{
+    /// Inserts a site, updating the triangles, centers, polygons,
+    /// neighbors and edges around it.
+    ///
+    /// The point ( in degrees ) is appended to the input points. When it
+    /// is within `tolerance` ( an angle in radians ) of a site, or at the
+    /// same location when `tolerance` is zero, it is merged into that site
+    /// and no cell changes.
+    ///
+    /// The update is local when the triangulation covers the sphere and
+    /// the cells were computed, otherwise the triangulation is rebuilt,
+    /// with the [`super::Structures`] it was constructed with.
+    /// A local update leaves the wrapped `delaunay` object stale, a
+    /// rebuild replaces it.
+    ///
+    /// # Errors
+    ///  When the point is not finite, or when a rebuild fails, in which
+    ///  case the triangulation is unchanged.
+    pub fn insert_site(
+        &mut self,
+        point: &Coord<T>,
+        tolerance: T,
+    ) -> Result<Update, UpdateError> {
+        if !is_finite(point) {
+            return Err(UpdateError::NonFiniteSite);
+        }
+        let v = cartesian(point);
+        let start = self
+            .neighbors
+            .iter()
+            .find(|(_, row)| !row.is_empty())
+            .map(|(site, _)| *site);
+        if let Some(start) = start {
+            let nearest = self.nearest(&v, start);
+            let merge = if tolerance > T::zero() {
+                dot(&v, &self.vectors[nearest]).min(T::one()).acos()
+                    <= tolerance
+            } else {
+                self.points[nearest] == *point
+            };
+            if merge {
+                self.index.push(nearest);
+                return Ok(Update {
+                    site: nearest,
+                    changed: Vec::new(),
+                    rebuilt: false,
+                });
+            }
+            if self.is_closed() {
+                if let Some(update) = self.insert_locally(point, v, nearest) {
+                    self.index.push(update.site);
+                    return Ok(update);
+                }
+            }
+        }
+
+        let site = self.points.len();
+        self.urquhart = Box::new(|_: &Vec<T>| vec![]);
+        Rc::make_mut(&mut self.points).push(*point);
+        Rc::make_mut(&mut self.vectors).push(v);
+        match self.rebuild() {
+            Ok(update) => {
+                self.index.push(site);
+                Ok(Update { site, ..update })
+            }
+            Err(e) => {
+                Rc::make_mut(&mut self.points).pop();
+                Rc::make_mut(&mut self.vectors).pop();
+                self.urquhart =
+                    urquhart(self.edges.clone(), self.triangles.clone());
+                Err(UpdateError::Rebuild(e))
+            }
+        }
+    }
+
+    /// Removes a site, updating the triangles, centers, polygons,
+    /// neighbors and edges around it.
+    ///
+    /// The site keeps its index, with non-finite coordinates and an empty
+    /// cell, and the input points merged into it still map onto it.
+    ///
+    /// The update is local when the triangulation covers the sphere and
+    /// the cells were computed, otherwise the triangulation is rebuilt,
+    /// with the [`super::Structures`] it was constructed with.
+    /// A local update leaves the wrapped `delaunay` object stale, a
+    /// rebuild replaces it.
+    ///
+    /// # Errors
+    ///  When there is no such site, or when a rebuild fails, in which case
+    ///  the triangulation is unchanged.
+    pub fn remove_site(&mut self, site: usize) -> Result<Update, UpdateError> {
+        if !self.points.get(site).is_some_and(is_finite) {
+            return Err(UpdateError::NoSuchSite { site });
+        }
+        if self.is_closed() && self.cells > 4 {
+            if let Some(update) = self.remove_locally(site) {
+                return Ok(update);
+            }
+        }
+
+        let (point, vector) = (self.points[site], self.vectors[site]);
+        self.urquhart = Box::new(|_: &Vec<T>| vec![]);
+        Rc::make_mut(&mut self.points)[site] = Coord {
+            x: T::nan(),
+            y: T::nan(),
+        };
+        Rc::make_mut(&mut self.vectors)[site] = [T::nan(); 3];
+        match self.rebuild() {
+            Ok(mut update) => {
+                update.changed.push(site);
+                update.changed.sort_unstable();
+                Ok(Update { site, ..update })
+            }
+            Err(e) => {
+                Rc::make_mut(&mut self.points)[site] = point;
+                Rc::make_mut(&mut self.vectors)[site] = vector;
+                self.urquhart =
+                    urquhart(self.edges.clone(), self.triangles.clone());
+                Err(UpdateError::Rebuild(e))
+            }
+        }
+    }
+
+    /// True when the triangles cover the sphere and every cell is made of
+    /// circumcenters, as required by the local updates.
+    fn is_closed(&self) -> bool {
+        self.polygons.len() == self.points.len()
+            && self.cells >= 4
+            && self.triangles.len() + 4 == 2 * self.cells
+            && self.centers.len() == self.triangles.len()
+    }
+
+    /// The edges of the cells of `sites` with an end among the centers
+    /// `slots`, as they appear in `mesh`.
+    fn mesh_edges(
+        &self,
+        sites: &[usize],
+        slots: &HashSet<usize>,
+    ) -> HashSet<EdgeIndex> {
+        let mut edges = HashSet::new();
+        for &w in sites {
+            let ring = self.cell(w);
+            for k in 0..ring.len() {
+                let (p, q) = (ring[k], ring[(k + 1) % ring.len()]);
+                if p != q && (slots.contains(&p) || slots.contains(&q)) {
+                    edges.insert((p.min(q), p.max(q)));
+                }
+            }
+        }
+        edges
+    }
+
+    /// Replaces the `stale` edges of the mesh with the `fresh` ones.
+    fn patch_mesh(
+        &mut self,
+        stale: &HashSet<EdgeIndex>,
+        fresh: &HashSet<EdgeIndex>,
+    ) {
+        if self.mesh_index.len() != self.mesh.len() {
+            self.mesh_index =
+                self.mesh.iter().enumerate().map(|(k, e)| (*e, k)).collect();
+        }
+        for e in stale.difference(fresh) {
+            if let Some(k) = self.mesh_index.remove(e) {
+                self.mesh.swap_remove(k);
+                if let Some(moved) = self.mesh.get(k) {
+                    self.mesh_index.insert(*moved, k);
+                }
+            }
+        }
+        for e in fresh.difference(stale) {
+            if !self.mesh_index.contains_key(e) {
+                self.mesh_index.insert(*e, self.mesh.len());
+                self.mesh.push(*e);
+            }
+        }
+    }
+
+    /// True when the unit vector `v` lies inside the circumcircle of the
+    /// triangle `t`.
+    fn in_circle(&self, tri: &TriIndex, v: &[T; 3]) -> bool {
+        let c = circumcenter(&tri.map(|i| self.vectors[i]));
+        dot(&c, v) > dot(&c, &self.vectors[tri[0]])
+    }
+
+    /// Orders the triangles around site `w`, as `polygons::gen()` does.
+    fn ring(
+        w: usize,
+        triangles: &[usize],
+        tri_at: &dyn Fn(usize) -> TriIndex,
+    ) -> Option<Ring> {
+        let mut next: HashMap<usize, (usize, usize)> = HashMap::new();
+        let mut first = None;
+        for &t in triangles {
+            let tri = tri_at(t);
+            let j = tri.iter().position(|&i| i == w)?;
+            let b = tri[(j + 1) % 3];
+            next.insert(b, (tri[(j + 2) % 3], t));
+            first.get_or_insert(b);
+        }
+        let first = first?;
+        let (mut polygon, mut neighbors) = (Vec::new(), Vec::new());
+        let mut b = first;
+        loop {
+            let (c, t) = *next.get(&b)?;
+            polygon.push(t);
+            neighbors.push(b);
+            b = c;
+            if b == first || polygon.len() > triangles.len() {
+                break;
+            }
+        }
+        if polygon.len() != triangles.len() {
+            return None;
+        }
+        // Closed, as `polygons::gen()` returns them.
+        polygon.push(polygon[0]);
+        Some((polygon, neighbors))
+    }
+
+    /// The new cells of `sites`, once the `removed` triangles are replaced
+    /// by the `added` ones, keyed by slot.
+    fn rings(
+        &self,
+        sites: &[usize],
+        removed: &[usize],
+        added: &HashMap<usize, TriIndex>,
+    ) -> Option<Vec<Ring>> {
+        let tri_at = |t: usize| {
+            added.get(&t).copied().unwrap_or_else(|| self.triangles[t])
+        };
+        let mut rings = Vec::with_capacity(sites.len());
+        for &w in sites {
+            let mut triangles: Vec<usize> = self
+                .cell(w)
+                .iter()
+                .copied()
+                .filter(|t| !removed.contains(t))
+                .collect();
+            let mut fan: Vec<usize> = added
+                .iter()
+                .filter(|(_, tri)| tri.contains(&w))
+                .map(|(t, _)| *t)
+                .collect();
+            fan.sort_unstable();
+            triangles.extend(fan);
+            rings.push(Self::ring(w, &triangles, &tri_at)?);
+        }
+        Some(rings)
+    }
+
+    /// Replaces the `removed` triangles by the `added` ones, and the cells
+    /// of `sites` by their `rings`.
+    ///
+    /// The urquhart closure must have released the edges and triangles.
+    fn replace(
+        &mut self,
+        removed: &[usize],
+        added: &HashMap<usize, TriIndex>,
+        sites: &[usize],
+        rings: Vec<Ring>,
+    ) {
+        let edges = Rc::make_mut(&mut self.edges);
+        for &t in removed {
+            let tri = self.triangles[t];
+            for j in 0..3 {
+                edges.remove(&extent(vec![tri[j], tri[(j + 1) % 3]], None));
+            }
+        }
+        let triangles = Rc::make_mut(&mut self.triangles);
+        let mut slots: Vec<(&usize, &TriIndex)> = added.iter().collect();
+        slots.sort_unstable();
+        for (&slot, &tri) in slots {
+            let center =
+                spherical(&circumcenter(&tri.map(|i| self.vectors[i])));
+            if slot < triangles.len() {
+                triangles[slot] = tri;
+                self.centers[slot] = center;
+            } else {
+                triangles.push(tri);
+                self.centers.push(center);
+            }
+            for j in 0..3 {
+                edges.insert(extent(vec![tri[j], tri[(j + 1) % 3]], None));
+            }
+        }
+        self.polygons.resize(self.points.len(), Vec::new());
+        let neighbors = Rc::make_mut(&mut self.neighbors);
+        for (&w, (polygon, row)) in sites.iter().zip(rings) {
+            self.polygons[w] = polygon;
+            neighbors.insert(w, row);
+        }
+    }
+
+    /// Inserts a site by replacing the triangles whose circumcircle holds
+    /// it ( the cavity ) with a fan around the new site.
+    ///
+    /// Returns None, leaving the triangulation unchanged, when the cavity
+    /// cannot be found.
+    fn insert_locally(
+        &mut self,
+        point: &Coord<T>,
+        v: [T; 3],
+        nearest: usize,
+    ) -> Option<Update> {
+        let n = self.points.len();
+
+        // The triangles next to the nearest site include one of the cavity.
+        let seed = self
+            .cell(nearest)
+            .iter()
+            .copied()
+            .find(|&t| self.in_circle(&self.triangles[t], &v))?;
+        let mut cavity = vec![seed];
+        let mut k = 0;
+        while k < cavity.len() {
+            let tri = self.triangles[cavity[k]];
+            for j in 0..3 {
+                let u = self.adjacent(cavity[k], tri[j], tri[(j + 1) % 3])?;
+                if !cavity.contains(&u)
+                    && self.in_circle(&self.triangles[u], &v)
+                {
+                    cavity.push(u);
+                }
+            }
+            k += 1;
+        }
+        let mut boundary = Vec::new();
+        for &t in &cavity {
+            let tri = self.triangles[t];
+            for j in 0..3 {
+                let (a, b) = (tri[j], tri[(j + 1) % 3]);
+                if !cavity.contains(&self.adjacent(t, a, b)?) {
+                    boundary.push((a, b));
+                }
+            }
+        }
+        if boundary.len() != cavity.len() + 2 {
+            return None;
+        }
+
+        // The fan reuses the cavity's slots.
+        let vector = |i: usize| if i == n { v } else { self.vectors[i] };
+        let mut added: HashMap<usize, TriIndex> = HashMap::new();
+        for (i, &(a, b)) in boundary.iter().enumerate() {
+            let tri = [a, b, n];
+            if excess_cartesian(&tri.map(vector)) <= T::zero() {
+                return None;
+            }
+            let slot = cavity
+                .get(i)
+                .copied()
+                .unwrap_or(self.triangles.len() + i - cavity.len());
+            added.insert(slot, tri);
+        }
+        let mut changed: Vec<usize> = boundary.iter().map(|e| e.0).collect();
+        changed.push(n);
+        let rings = self.rings(&changed, &cavity, &added)?;
+        let slots: HashSet<usize> = added.keys().copied().collect();
+        let stale = self.mesh_edges(&changed, &slots);
+
+        self.urquhart = Box::new(|_: &Vec<T>| vec![]);
+        Rc::make_mut(&mut self.points).push(*point);
+        Rc::make_mut(&mut self.vectors).push(v);
+        self.replace(&cavity, &added, &changed, rings);
+        self.cells += 1;
+        let fresh = self.mesh_edges(&changed, &slots);
+        self.patch_mesh(&stale, &fresh);
+        self.urquhart = urquhart(self.edges.clone(), self.triangles.clone());
+
+        changed.sort_unstable();
+        Some(Update {
+            site: n,
+            changed,
+            rebuilt: false,
+        })
+    }
+
+    /// Retriangulates the hole bounded by `link`, clipping ears whose
+    /// circumcircle holds no other vertex of the hole when there is one.
+    fn fill(&self, link: &[usize]) -> Option<Vec<TriIndex>> {
+        let epsilon = T::epsilon() * T::from(64_f64)?;
+        let is_ear = |ring: &[usize], i: usize, delaunay: bool| {
+            let m = ring.len();
+            let tri = [ring[(i + m - 1) % m], ring[i], ring[(i + 1) % m]];
+            let c = tri.map(|i| self.vectors[i]);
+            if excess_cartesian(&c) <= T::zero() {
+                return false;
+            }
+            if !delaunay {
+                return true;
+            }
+            let center = circumcenter(&c);
+            let r = dot(&center, &c[0]);
+            ring.iter()
+                .filter(|d| !tri.contains(d))
+                .all(|&d| dot(&center, &self.vectors[d]) <= r + epsilon)
+        };
+        let mut ring = link.to_vec();
+        let mut fill = Vec::with_capacity(link.len() - 2);
+        while ring.len() > 3 {
+            let m = ring.len();
+            let ear = (0..m)
+                .find(|&i| is_ear(&ring, i, true))
+                .or_else(|| (0..m).find(|&i| is_ear(&ring, i, false)))?;
+            fill.push([
+                ring[(ear + m - 1) % m],
+                ring[ear],
+                ring[(ear + 1) % m],
+            ]);
+            ring.remove(ear);
+        }
+        if !is_ear(&ring, 1, false) {
+            return None;
+        }
+        fill.push([ring[0], ring[1], ring[2]]);
+        Some(fill)
+    }
+
+    /// Removes a site by retriangulating the hole left by its triangles.
+    ///
+    /// Returns None, leaving the triangulation unchanged, when the hole
+    /// cannot be retriangulated.
+    fn remove_locally(&mut self, site: usize) -> Option<Update> {
+        let removed = self.cell(site).to_vec();
+        let link: Vec<usize> = removed
+            .iter()
+            .map(|&t| {
+                let tri = self.triangles[t];
+                let j = tri.iter().position(|&i| i == site).unwrap_or(0);
+                tri[(j + 1) % 3]
+            })
+            .collect();
+        let added: HashMap<usize, TriIndex> =
+            removed.iter().copied().zip(self.fill(&link)?).collect();
+        let rings = self.rings(&link, &removed, &added)?;
+        // The last triangles move into the freed slots.
+        let tail = self.triangles.len() + added.len() - removed.len();
+        let mut slots: HashSet<usize> = removed.iter().copied().collect();
+        slots.extend(tail..self.triangles.len());
+        let mut sites = link.clone();
+        sites.push(site);
+        sites.extend(self.triangles[tail..].iter().flatten());
+        let stale = self.mesh_edges(&sites, &slots);
+
+        self.urquhart = Box::new(|_: &Vec<T>| vec![]);
+        self.replace(&removed, &added, &link, rings);
+        self.polygons[site] = Vec::new();
+        self.cells -= 1;
+        Rc::make_mut(&mut self.neighbors).remove(&site);
+
+        // Fill the two freed slots with the last triangles.
+        let triangles = Rc::make_mut(&mut self.triangles);
+        let mut freed = removed[added.len()..].to_vec();
+        freed.sort_unstable_by(|a, b| b.cmp(a));
+        for f in freed {
+            let last = triangles.len() - 1;
+            if f != last {
+                triangles[f] = triangles[last];
+                self.centers[f] = self.centers[last];
+                for w in triangles[f] {
+                    for t in &mut self.polygons[w] {
+                        if *t == last {
+                            *t = f;
+                        }
+                    }
+                }
+            }
+            triangles.pop();
+            self.centers.pop();
+        }
+
+        Rc::make_mut(&mut self.points)[site] = Coord {
+            x: T::nan(),
+            y: T::nan(),
+        };
+        Rc::make_mut(&mut self.vectors)[site] = [T::nan(); 3];
+        let fresh = self.mesh_edges(&sites, &slots);
+        self.patch_mesh(&stale, &fresh);
+        self.urquhart = urquhart(self.edges.clone(), self.triangles.clone());
+
+        let mut changed = link;
+        changed.push(site);
+        changed.sort_unstable();
+        Some(Update {
+            site,
+            changed,
+            rebuilt: false,
+        })
+    }
+
+    /// Triangulates the sites with finite coordinates from scratch,
+    /// keeping their indices and computing the structures computed
+    /// before.
+    fn rebuild(&mut self) -> Result<Update, ConstructionError> {
+        let live: Vec<usize> = (0..self.points.len())
+            .filter(|&i| is_finite(&self.points[i]))
+            .collect();
+        let points: Vec<Coord<T>> =
+            live.iter().map(|&i| self.points[i]).collect();
+        let vectors: Vec<[T; 3]> =
+            live.iter().map(|&i| self.vectors[i]).collect();
+        let d = Self::try_with_vectors(
+            &points,
+            &vectors,
+            T::zero(),
+            self.structures,
+        )?;
+
+        // Maps the sites of the new triangulation onto the current ones.
+        let mut map = Vec::with_capacity(d.points.len());
+        for (k, &j) in d.index.iter().enumerate() {
+            if j == map.len() {
+                map.push(live[k]);
+            }
+        }
+        let mut polygons = if self.structures.polygons {
+            vec![Vec::new(); self.points.len()]
+        } else {
+            Vec::new()
+        };
+        for (j, polygon) in d.polygons.into_iter().enumerate() {
+            polygons[map[j]] = polygon;
+        }
+        self.delaunay = d.delaunay;
+        self.triangles =
+            Rc::new(d.triangles.iter().map(|t| t.map(|i| map[i])).collect());
+        self.centers = d.centers;
+        self.polygons = polygons;
+        self.neighbors = Rc::new(
+            d.neighbors
+                .iter()
+                .map(|(k, row)| {
+                    (map[*k], row.iter().map(|i| map[*i]).collect())
+                })
+                .collect(),
+        );
+        self.edges =
+            Rc::new(d.edges.iter().map(|&(a, b)| (map[a], map[b])).collect());
+        self.mesh = d.mesh;
+        self.mesh_index.clear();
+        self.cells = d.cells;
+        self.hull = d.hull.iter().map(|&i| map[i]).collect();
+        self.urquhart = urquhart(self.edges.clone(), self.triangles.clone());
+
+        Ok(Update {
+            site: 0,
+            changed: map,
+            rebuilt: true,
+        })
+    }
+}
@@ -0,0 +1,113 @@
+use d3_geo_rs::cartesian::cross;
+use d3_geo_rs::cartesian::dot;
+use geo::CoordFloat;
+use geo_types::Coord;
+
+use super::cartesian::cartesian;
+use super::Delaunay;
+
+impl<T> Delaunay<T>
+where
+    T: CoordFloat,
+{
+    /// Returns the triangle holding `p`, in degrees, with the spherical
+    /// barycentric coordinates of `p` in that triangle.
+    ///
+    /// The coordinates weight the vertices in the order of `triangles`.
+    /// They are the barycentric coordinates of the central projection of
+    /// `p` onto the plane of the triangle, so they are not negative and
+    /// sum to one.
+    ///
+    /// The search walks the triangles from one around the site nearest to
+    /// `p`, and scans them when the cells were not computed, or when the
+    /// walk cycles through rounding. Returns None when `p` is not finite,
+    /// or lies outside the triangles, which only happens when the sites lie
+    /// in a hemisphere.
+    #[must_use]
+    pub fn locate(&self, p: &Coord<T>) -> Option<(usize, [T; 3])> {
+        self.locate_from(p, None)
+    }
+
+    /// As [`Delaunay::locate`], walking from the triangle `hint`, the
+    /// result of a query close to `p`.
+    #[must_use]
+    pub fn locate_from(
+        &self,
+        p: &Coord<T>,
+        hint: Option<usize>,
+    ) -> Option<(usize, [T; 3])> {
+        if !(p.x + p.y).is_finite() {
+            return None;
+        }
+        let xyz = cartesian(p);
+        // Points on an edge, or at a site, get weights slightly below
+        // zero through rounding.
+        let tolerance = T::epsilon() * (T::one() + T::one()).powi(6);
+        let walk = !self.polygons.is_empty();
+        let start =
+            hint.filter(|&t| walk && t < self.triangles.len())
+                .or_else(|| {
+                    let site = self.find(p, None)?;
+                    self.cell(site)
+                        .iter()
+                        .copied()
+                        .find(|&t| t < self.triangles.len())
+                });
+        let Some(mut t) = start else {
+            return self.scan(&xyz, tolerance);
+        };
+
+        for _ in 0..self.triangles.len() {
+            let weights = self.barycentric(t, &xyz);
+            // Step across the edge opposite the most negative weight.
+            let (k, w) = weights.iter().copied().enumerate().fold(
+                (0, T::zero()),
+                |min, (k, w)| {
+                    if w < min.1 {
+                        (k, w)
+                    } else {
+                        min
+                    }
+                },
+            );
+            if w >= -tolerance {
+                return Some((t, normalized(weights)));
+            }
+            let tri = self.triangles[t];
+            // None when stepping off the hull.
+            t = self.adjacent(t, tri[(k + 1) % 3], tri[(k + 2) % 3])?;
+        }
+        // Rounding made the walk cycle.
+        self.scan(&xyz, tolerance)
+    }
+
+    /// Returns the first triangle holding the unit vector `xyz`, testing
+    /// each in turn.
+    fn scan(&self, xyz: &[T; 3], tolerance: T) -> Option<(usize, [T; 3])> {
+        (0..self.triangles.len()).find_map(|t| {
+            let weights = self.barycentric(t, xyz);
+            weights
+                .iter()
+                .all(|w| *w >= -tolerance)
+                .then(|| (t, normalized(weights)))
+        })
+    }
+
+    /// The coordinates of the unit vector `xyz` in the basis of the
+    /// vertices of triangle `t`.
+    fn barycentric(&self, t: usize, xyz: &[T; 3]) -> [T; 3] {
+        let [a, b, c] = self.triangles[t].map(|i| self.vectors[i]);
+        let det = dot(&a, &cross(&b, &c));
+        [
+            dot(xyz, &cross(&b, &c)) / det,
+            dot(xyz, &cross(&c, &a)) / det,
+            dot(xyz, &cross(&a, &b)) / det,
+        ]
+    }
+}
+
+fn normalized<T: CoordFloat>(weights: [T; 3]) -> [T; 3] {
+    let weights = weights.map(|w| w.max(T::zero()));
+    let sum = weights[0] + weights[1] + weights[2];
+    weights.map(|w| w / sum)
+}
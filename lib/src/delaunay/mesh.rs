@@ -6,8 +6,9 @@ pub fn mesh(polygons: &[Vec<usize>]) -> Vec<EdgeIndex> {
     // from constant reallocation.
     let mut mesh = Vec::with_capacity(polygons.len());
     for poly in polygons {
+        // Sites absent from the triangulation have no cell.
         if poly.is_empty() {
-            return Vec::new();
+            continue;
         }
         let mut p: usize = *poly.last().unwrap();
         for q in poly {
@@ -1,7 +1,12 @@
 #![allow(clippy::many_single_char_names)]
-mod cartesian;
+pub(crate) mod cartesian;
 
-mod circumcenters;
+mod centroids;
+pub(crate) mod circumcenters;
+mod clearance;
+mod clip;
+/// Merges coincident points.
+pub mod dedup;
 mod edges;
 /// A helper function.
 pub mod excess;
@@ -9,35 +14,49 @@ mod find;
 /// Helper function.
 pub mod generate;
 mod hull;
+mod incremental;
+mod locate;
 mod mesh;
 mod neighbors;
 mod o_midpoint;
 mod polygons;
+mod skeleton;
+mod stats;
 mod triangles;
 mod urquhart;
+mod within;
 
 use core::fmt::Debug;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::rc::Rc;
 
+use d3_geo_rs::cartesian::normalize;
+use d3_geo_rs::cartesian::spherical;
 use geo::CoordFloat;
 use geo_types::Coord;
 use num_traits::FloatConst;
 use num_traits::FromPrimitive;
 
+use cartesian::cartesian;
 use circumcenters::circumcenters;
+pub use clearance::ClearancePath;
+use dedup::dedup_vectors;
 use edges::edges;
-use generate::from_points;
+use generate::from_sites;
 use hull::hull;
+pub use incremental::Update;
 use mesh::mesh;
 use neighbors::neighbors;
 use polygons::gen;
+pub use stats::CellStats;
 use triangles::triangles;
 use urquhart::urquhart;
 
 use d3_delaunay_rs::delaunay::Delaunay as DelaunayInner;
 
+use crate::error::ConstructionError;
+
 /// A Pair of indices pointing into a dataset identifying a edge.
 type EdgeIndex = (usize, usize);
 
@@ -51,8 +70,18 @@ pub struct Delaunay<T>
 where
     T: CoordFloat,
 {
-    /// The underlying delaunay object
+    /// The underlying delaunay object.
+    ///
+    /// Stale after a local [`Delaunay::insert_site`] or
+    /// [`Delaunay::remove_site`], replaced when they rebuild.
     pub delaunay: DelaunayInner<T>,
+    /// The sites, what remains of the input points once coincident points
+    /// are merged. All other fields index into this vec.
+    pub points: Rc<Vec<Coord<T>>>,
+    /// The unit vector of each site.
+    pub vectors: Rc<Vec<[T; 3]>>,
+    /// Maps each input point onto its site.
+    pub index: Vec<usize>,
     /// The edges and triangles properties need RC because the values are close over in the urquhart function.
     pub edges: Rc<HashSet<EdgeIndex>>,
     /// A set of triangles as defined by set of indices.
@@ -69,6 +98,13 @@ where
     pub hull: Vec<usize>,
     /// Urquhart graph .. by index the set the of points in the plane.
     pub urquhart: UTransform<T>,
+    // The number of sites with a cell, kept by the incremental updates.
+    cells: usize,
+    // The position of each edge in `mesh`, built by the first incremental
+    // update which patches it.
+    mesh_index: HashMap<EdgeIndex, usize>,
+    // The structures computed, kept by an incremental update which rebuilds.
+    structures: Structures,
     // /// Returns the indexes of the points.
     // pub find: FindReturn<'a, T>,
 }
@@ -79,6 +115,9 @@ where
 {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_tuple("Delaunay")
+            .field(&self.points)
+            .field(&self.vectors)
+            .field(&self.index)
             .field(&self.edges)
             .field(&self.triangles)
             .field(&self.centers)
@@ -97,6 +136,9 @@ where
     fn default() -> Self {
         Self {
             delaunay: DelaunayInner::new(&[]),
+            points: Rc::new(vec![]),
+            vectors: Rc::new(vec![]),
+            index: vec![],
             edges: Rc::new(HashSet::new()),
             triangles: Rc::new(vec![]),
             centers: vec![],
@@ -105,50 +147,166 @@ where
             mesh: vec![],
             urquhart: Box::new(|_: &Vec<T>| vec![]),
             hull: vec![],
+            cells: 0,
+            mesh_index: HashMap::new(),
+            structures: Structures::default(),
         }
     }
 }
 
-/// An error type returned when trying to generate a Voronoi object.
+/// Selects which of the derived structures are computed.
 ///
-/// For example if an insufficient number of point was supplied.
-#[derive(Debug)]
-pub struct NotEnoughPointsError {}
+/// Edges, triangles, centers and neighbors are always computed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Structures {
+    /// The cells ( `polygons` ) and their `mesh`.
+    pub polygons: bool,
+    /// The `hull`.
+    pub hull: bool,
+}
+
+impl Default for Structures {
+    fn default() -> Self {
+        Self {
+            polygons: true,
+            hull: true,
+        }
+    }
+}
 
 impl<T> TryFrom<&Vec<Coord<T>>> for Delaunay<T>
 where
     T: 'static + CoordFloat + Default + FloatConst + FromPrimitive,
 {
-    type Error = NotEnoughPointsError;
+    type Error = ConstructionError;
 
     /// Creates a `GeoDelaunay` object from a set of points.
-    fn try_from(points: &Vec<Coord<T>>) -> Result<Self, NotEnoughPointsError> {
-        from_points(points).map_or_else(
-            || Err(NotEnoughPointsError {}),
-            |delaunay| {
-                // RC is needed here as tri and e are both closed over in the urquhart function an is part of the Delaunay return.
-                let tri = Rc::new(triangles(&delaunay));
-                let e = Rc::new(edges(&tri, points));
-                let circumcenters = circumcenters(&tri, points);
-                let (polys, centers) =
-                    gen(circumcenters.collect(), tri.clone(), points);
-
-                // RC is needed here as it is both closed over in the find function an is part of the Delaunay return.
-                let neighbors = Rc::new(neighbors(&tri, points.len()));
-
-                Ok(Self {
-                    delaunay,
-                    edges: e.clone(),
-                    centers,
-                    hull: hull(&tri, points),
-                    // find: find(n.clone(), points),
-                    neighbors,
-                    mesh: mesh(&polys),
-                    polygons: polys,
-                    urquhart: urquhart(e, tri.clone()),
-                    triangles: tri,
-                })
-            },
-        )
+    ///
+    /// # Errors
+    ///  See [`generate::from_points`].
+    fn try_from(points: &Vec<Coord<T>>) -> Result<Self, ConstructionError> {
+        Self::try_with_structures(points, &Structures::default())
+    }
+}
+
+impl<T> Delaunay<T>
+where
+    T: 'static + CoordFloat + Default + FloatConst + FromPrimitive,
+{
+    /// Creates a `GeoDelaunay` object from a set of points, computing
+    /// only the selected derived structures.
+    ///
+    /// Points with identical coordinates are merged into one site.
+    ///
+    /// # Errors
+    ///  See [`generate::from_points`].
+    pub fn try_with_structures(
+        points: &[Coord<T>],
+        structures: &Structures,
+    ) -> Result<Self, ConstructionError> {
+        Self::try_with_tolerance(points, T::zero(), structures)
+    }
+
+    /// Creates a `GeoDelaunay` object from a set of points, computing
+    /// only the selected derived structures.
+    ///
+    /// Points within `tolerance` ( an angle in radians ) of an earlier
+    /// point are merged into its site, see [`dedup::dedup`].
+    ///
+    /// # Errors
+    ///  See [`generate::from_points`].
+    pub fn try_with_tolerance(
+        points: &[Coord<T>],
+        tolerance: T,
+        structures: &Structures,
+    ) -> Result<Self, ConstructionError> {
+        let vectors: Vec<[T; 3]> = points.iter().map(cartesian).collect();
+        Self::try_with_vectors(points, &vectors, tolerance, *structures)
+    }
+
+    /// Creates a `GeoDelaunay` object from a set of 3D cartesian vectors.
+    ///
+    /// The vectors are normalized, and used as is in the geometric
+    /// computations, avoiding a round trip through spherical coordinates.
+    /// A zero or non-finite vector has a non-finite site.
+    ///
+    /// # Errors
+    ///  See [`generate::from_points`].
+    pub fn from_unit_vectors(
+        vectors: &[[T; 3]],
+    ) -> Result<Self, ConstructionError> {
+        Self::try_from_unit_vectors(vectors, T::zero(), &Structures::default())
+    }
+
+    /// As [`Delaunay::from_unit_vectors`], merging vectors within
+    /// `tolerance` and computing only the selected derived structures.
+    ///
+    /// # Errors
+    ///  See [`generate::from_points`].
+    pub fn try_from_unit_vectors(
+        vectors: &[[T; 3]],
+        tolerance: T,
+        structures: &Structures,
+    ) -> Result<Self, ConstructionError> {
+        let vectors: Vec<[T; 3]> = vectors.iter().map(normalize).collect();
+        let points: Vec<Coord<T>> = vectors.iter().map(spherical).collect();
+        Self::try_with_vectors(&points, &vectors, tolerance, *structures)
+    }
+
+    /// As [`Delaunay::try_with_tolerance`], with the unit vector of each
+    /// point supplied.
+    pub(crate) fn try_with_vectors(
+        points: &[Coord<T>],
+        vectors: &[[T; 3]],
+        tolerance: T,
+        structures: Structures,
+    ) -> Result<Self, ConstructionError> {
+        let (sites, index) = dedup_vectors(points, vectors, tolerance);
+        let finite = |p: &&Coord<T>| (p.x + p.y).is_finite();
+        if sites.iter().filter(|&&i| finite(&&points[i])).count() == 1
+            && points.iter().filter(finite).count() > 1
+        {
+            return Err(ConstructionError::AllSitesCoincident);
+        }
+        let vectors: Vec<[T; 3]> = sites.iter().map(|&i| vectors[i]).collect();
+        let points: Vec<Coord<T>> = sites.iter().map(|&i| points[i]).collect();
+        from_sites(&points, &vectors).map(|delaunay| {
+            // RC is needed here as tri and e are both closed over in the urquhart function an is part of the Delaunay return.
+            let tri = Rc::new(triangles(&delaunay));
+            let e = Rc::new(edges(&tri, &vectors));
+            let circumcenters: Vec<Coord<T>> =
+                circumcenters(&tri, &vectors).collect();
+            let (polys, centers) = if structures.polygons {
+                gen(circumcenters, tri.clone(), &vectors)
+            } else {
+                (vec![], circumcenters)
+            };
+
+            // RC is needed here as it is both closed over in the find function an is part of the Delaunay return.
+            let neighbors = Rc::new(neighbors(&tri, points.len()));
+
+            Self {
+                delaunay,
+                index,
+                edges: e.clone(),
+                centers,
+                hull: if structures.hull {
+                    hull(&tri, &vectors)
+                } else {
+                    vec![]
+                },
+                // find: find(n.clone(), points),
+                neighbors,
+                mesh: mesh(&polys),
+                cells: polys.iter().filter(|p| !p.is_empty()).count(),
+                mesh_index: HashMap::new(),
+                structures,
+                polygons: polys,
+                urquhart: urquhart(e, tri.clone()),
+                triangles: tri,
+                points: Rc::new(points),
+                vectors: Rc::new(vectors),
+            }
+        })
     }
 }
@@ -11,12 +11,11 @@ use num_traits::FloatConst;
 
 use super::cartesian::cartesian;
 
-pub fn o_midpoint<T>(a: &Coord<T>, b: &Coord<T>, c: &Coord<T>) -> Coord<T>
+/// `a` and `b` are unit vectors, `c` is in spherical coordinates.
+pub fn o_midpoint<T>(a: &[T; 3], b: &[T; 3], c: &Coord<T>) -> Coord<T>
 where
     T: CoordFloat + FloatConst,
 {
-    let a = &cartesian(a);
-    let b = &cartesian(b);
     let c = &cartesian(c);
     let s = (dot(&cross(b, a), c)).signum();
 
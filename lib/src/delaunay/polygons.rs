@@ -11,11 +11,11 @@ use geo::CoordFloat;
 use geo_types::Coord;
 use num_traits::FloatConst;
 
-use super::cartesian::cartesian;
 use super::o_midpoint::o_midpoint;
 use super::TriIndex;
 
-type TupleVec = Vec<(usize, usize, usize, (usize, usize, usize))>;
+type TupleEntry = (usize, usize, usize, (usize, usize, usize));
+type TupleVec = Vec<TupleEntry>;
 
 fn supplement<T>(
     point: &Coord<T>,
@@ -34,14 +34,11 @@ where
                 f = Some(i + triangles_len);
             }
         });
-    f.map_or_else(
-        || {
-            let f_out: usize = centers.len();
-            centers.push(*point);
-            f_out
-        },
-        |f| f,
-    )
+    f.unwrap_or_else(|| {
+        let f_out: usize = centers.len();
+        centers.push(*point);
+        f_out
+    })
 }
 
 /// Looking at the flamegraph generated with `profile_target`.
@@ -51,7 +48,7 @@ where
 pub fn gen<T>(
     circumcenter: Vec<Coord<T>>,
     triangles_p: Rc<Vec<TriIndex>>,
-    points: &[Coord<T>],
+    vectors: &[[T; 3]],
 ) -> (Vec<Vec<usize>>, Vec<Coord<T>>)
 where
     T: CoordFloat + Debug + FloatConst,
@@ -60,15 +57,15 @@ where
     let triangles = triangles_p;
 
     if triangles.is_empty() {
-        if points.len() < 2 {
+        if vectors.len() < 2 {
             return (vec![], centers);
         }
         // // WARNING in the original javascript this block is never tested.
-        if points.len() == 2 {
+        if vectors.len() == 2 {
             let mut polygons: Vec<Vec<usize>> = Vec::new();
             // Two hemispheres.
-            let a = cartesian(&points[0]);
-            let b = cartesian(&points[1]);
+            let a = vectors[0];
+            let b = vectors[1];
             let m = normalize(&add(a, b));
 
             let d = normalize(&cross(&a, &b));
@@ -90,42 +87,33 @@ where
         }
     }
 
-    let mut polygons: HashMap<usize, TupleVec> =
-        HashMap::with_capacity(triangles.len());
+    // Indexed by site, so that `polygons[i]` is always the cell of site `i`.
+    let mut polygons: Vec<TupleVec> = vec![Vec::new(); vectors.len()];
     for (t, tri) in triangles.iter().enumerate() {
         for j in 0..3 {
             let a = tri[j];
             let b = tri[(j + 1) % 3];
             let c = tri[(j + 2) % 3];
 
-            let next = (b, c, t, (a, b, c));
-
-            #[allow(clippy::option_if_let_else)]
-            match polygons.get_mut(&a) {
-                Some(polygon) => {
-                    polygon.push(next);
-                }
-                None => {
-                    polygons.insert(a, vec![next]);
-                }
-            }
+            polygons[a].push((b, c, t, (a, b, c)));
         }
     }
 
     // Reorder each polygon.
     let reordered: Vec<Vec<usize>> = polygons
         .iter()
-        .map(|poly_ind| {
-            let poly = poly_ind.1;
+        .map(|poly| {
+            // Sites absent from the triangulation have no cell.
+            if poly.is_empty() {
+                return Vec::new();
+            }
             let mut p = vec![poly[0].2]; // t
             let mut k = poly[0].1; // k = c
 
             // Performance: Build lookup map once
             // TODO: use a faster hashmap here.
-            let lookup: HashMap<
-                usize,
-                &(usize, usize, usize, (usize, usize, usize)),
-            > = poly.iter().map(|pj| (pj.0, pj)).collect();
+            let lookup: HashMap<usize, &TupleEntry> =
+                poly.iter().map(|pj| (pj.0, pj)).collect();
 
             for _i in 0..poly.len() {
                 if let Some(pj) = lookup.get(&k) {
@@ -140,13 +128,13 @@ where
                 0 | 1 => Vec::new(),
                 2 => {
                     let r0 = o_midpoint(
-                        &points[(poly[0].3).0],
-                        &points[(poly[0].3).1],
+                        &vectors[(poly[0].3).0],
+                        &vectors[(poly[0].3).1],
                         &centers[p[0]],
                     );
                     let r1 = o_midpoint(
-                        &points[(poly[0].3).2],
-                        &points[(poly[0].3).0],
+                        &vectors[(poly[0].3).2],
+                        &vectors[(poly[0].3).0],
                         &centers[p[0]],
                     );
 
@@ -0,0 +1,138 @@
+use core::cmp::Ordering;
+
+use d3_geo_rs::cartesian::cross;
+use d3_geo_rs::cartesian::dot;
+use d3_geo_rs::cartesian::normalize;
+use d3_geo_rs::cartesian::spherical;
+use geo::CoordFloat;
+use num_traits::FloatConst;
+
+use super::within::angle;
+use super::Delaunay;
+
+/// The point at `s` radians from the unit vector `a`, towards the unit
+/// vector `t` orthogonal to `a`.
+fn towards<T: CoordFloat>(a: &[T; 3], t: &[T; 3], s: T) -> [T; 3] {
+    let (sin, cos) = s.sin_cos();
+    [
+        a[0] * cos + t[0] * sin,
+        a[1] * cos + t[1] * sin,
+        a[2] * cos + t[2] * sin,
+    ]
+}
+
+impl<T> Delaunay<T>
+where
+    T: CoordFloat + FloatConst,
+{
+    /// Flags the edges of the lune-based β-skeleton, in the order of
+    /// [`Delaunay::edges`].
+    ///
+    /// An edge `ab` of length `d` is kept when no other site lies inside
+    /// its lune, the intersection of the two caps of radius `βd / 2`
+    /// centered on the great circle through `a` and `b`, at `βd / 2` from
+    /// `a` and from `b`. Sites on the boundary of the lune do not block
+    /// the edge.
+    ///
+    /// A `beta` below 1 is taken as 1, as smaller values give graphs with
+    /// edges outside the triangulation.
+    #[must_use]
+    pub fn beta_skeleton(&self, beta: T) -> Vec<bool> {
+        let beta = beta.max(T::one());
+        self.edges
+            .iter()
+            .map(|&(a, b)| self.is_in_skeleton(a, b, beta))
+            .collect()
+    }
+
+    /// Flags the edges of the Gabriel graph, the β-skeleton for β = 1:
+    /// the cap with the edge as its diameter holds no other site.
+    #[must_use]
+    pub fn gabriel_graph(&self) -> Vec<bool> {
+        self.beta_skeleton(T::one())
+    }
+
+    /// Flags the edges of the relative neighborhood graph, the β-skeleton
+    /// for β = 2: no other site is closer to both ends than they are to
+    /// each other.
+    #[must_use]
+    pub fn relative_neighborhood_graph(&self) -> Vec<bool> {
+        self.beta_skeleton(T::one() + T::one())
+    }
+
+    /// Returns the nearest other site of each site, None for a site
+    /// without neighbors.
+    ///
+    /// Ties go to the lower index. The nearest site is always a Delaunay
+    /// neighbor.
+    #[must_use]
+    pub fn nearest_neighbors(&self) -> Vec<Option<usize>> {
+        (0..self.vectors.len())
+            .map(|site| {
+                let v = &self.vectors[site];
+                self.neighbors
+                    .get(&site)
+                    .into_iter()
+                    .flatten()
+                    .map(|&i| (dot(v, &self.vectors[i]), i))
+                    .max_by(|x, y| {
+                        x.0.partial_cmp(&y.0)
+                            .unwrap_or(Ordering::Equal)
+                            .then(y.1.cmp(&x.1))
+                    })
+                    .map(|(_, i)| i)
+            })
+            .collect()
+    }
+
+    /// Flags the edges of the nearest neighbor graph, in the order of
+    /// [`Delaunay::edges`]: the edges from a site to its nearest other
+    /// site, see [`Delaunay::nearest_neighbors`].
+    #[must_use]
+    pub fn nearest_neighbor_graph(&self) -> Vec<bool> {
+        let nearest = self.nearest_neighbors();
+        self.edges
+            .iter()
+            .map(|&(a, b)| nearest[a] == Some(b) || nearest[b] == Some(a))
+            .collect()
+    }
+
+    /// Whether no site other than `a` and `b` lies inside the lune of the
+    /// edge `ab`.
+    fn is_in_skeleton(&self, a: usize, b: usize, beta: T) -> bool {
+        let two = T::one() + T::one();
+        let (va, vb) = (&self.vectors[a], &self.vectors[b]);
+        let d = angle(va, vb);
+        let radius = beta * d / two;
+
+        // The unit tangents, at each end, towards the other end.
+        let n = cross(va, vb);
+        if dot(&n, &n) == T::zero() {
+            return true;
+        }
+        let ta = normalize(&cross(&n, va));
+        let tb = normalize(&cross(vb, &n));
+        let centers = [towards(va, &ta, radius), towards(vb, &tb, radius)];
+
+        // The lune lies within the cap centered on the midpoint of the
+        // edge, reaching its ends and the tips of the lune.
+        let mid = towards(va, &ta, d / two);
+        let offset = ((beta - T::one()) * d / two).cos();
+        let reach = if offset > T::zero() {
+            (radius.cos() / offset).max(-T::one()).min(T::one()).acos()
+        } else {
+            T::infinity()
+        };
+
+        let tolerance = T::epsilon() * two.powi(6);
+        let cos = radius.cos() + tolerance;
+        !self
+            .sites_within(&spherical(&mid), reach.max(d / two) + tolerance)
+            .into_iter()
+            .any(|(i, _)| {
+                i != a
+                    && i != b
+                    && centers.iter().all(|c| dot(c, &self.vectors[i]) > cos)
+            })
+    }
+}
@@ -0,0 +1,105 @@
+use geo::CoordFloat;
+use num_traits::FloatConst;
+
+use super::cartesian::cartesian;
+use super::clearance::ring_edges;
+use super::excess::area_cartesian;
+use super::within::angle;
+use super::within::arc_distance;
+use super::Delaunay;
+
+/// The size and shape of a cell.
+#[derive(Clone, Copy, Debug)]
+pub struct CellStats<T>
+where
+    T: CoordFloat,
+{
+    /// The area of the cell.
+    pub area: T,
+    /// The length of the boundary of the cell.
+    pub perimeter: T,
+    /// The number of vertices of the cell.
+    pub vertices: usize,
+    /// The isoperimetric quotient, `A (4π - A) / P²` on the unit sphere:
+    /// 1 for a cap, smaller for less compact cells.
+    pub compactness: T,
+    /// The radius of the largest cap around the site inside the cell.
+    pub inscribed_radius: T,
+    /// The radius of the smallest cap around the site holding the cell.
+    pub circumscribed_radius: T,
+}
+
+/// The area of a cell, fanned out from its site which sees all of it.
+fn fan_area<T: CoordFloat>(
+    s: &[T; 3],
+    edges: impl Iterator<Item = ([T; 3], [T; 3])>,
+) -> T {
+    edges
+        .map(|(a, b)| area_cartesian(&[*s, a, b]))
+        .fold(T::zero(), |acc, x| acc + x)
+        .abs()
+}
+
+impl<T> Delaunay<T>
+where
+    T: CoordFloat + FloatConst,
+{
+    /// Returns the area of the cell of `site` on the unit sphere, in
+    /// steradians.
+    ///
+    /// None for a site without a cell, see [`Delaunay::cell_stats`].
+    #[must_use]
+    pub fn cell_area(&self, site: usize) -> Option<T> {
+        let s = self.vectors.get(site)?;
+        let ring = self.polygons.get(site)?;
+        ring_edges(ring).next()?;
+        let edges = ring_edges(ring).map(|(a, b)| {
+            (cartesian(&self.centers[a]), cartesian(&self.centers[b]))
+        });
+        Some(fan_area(s, edges))
+    }
+
+    /// Returns the size and shape of the cell of each site, on the unit
+    /// sphere: areas in steradians and lengths in radians.
+    ///
+    /// None for a site without a cell. Empty when the cells were not
+    /// computed, see [`super::Structures`].
+    #[must_use]
+    pub fn cell_stats(&self) -> Vec<Option<CellStats<T>>> {
+        let vectors: Vec<[T; 3]> = self.centers.iter().map(cartesian).collect();
+        self.polygons
+            .iter()
+            .enumerate()
+            .map(|(site, ring)| {
+                let s = &self.vectors[site];
+                let edges: Vec<(&[T; 3], &[T; 3])> = ring_edges(ring)
+                    .map(|(a, b)| (&vectors[a], &vectors[b]))
+                    .collect();
+                if edges.is_empty() {
+                    return None;
+                }
+                let area = fan_area(s, edges.iter().map(|&(a, b)| (*a, *b)));
+                let perimeter = edges
+                    .iter()
+                    .map(|&(a, b)| angle(a, b))
+                    .fold(T::zero(), |acc, x| acc + x);
+                let four_pi = (T::one() + T::one()) * T::TAU();
+                Some(CellStats {
+                    area,
+                    perimeter,
+                    vertices: edges.len(),
+                    compactness: area * (four_pi - area)
+                        / (perimeter * perimeter),
+                    inscribed_radius: edges
+                        .iter()
+                        .map(|&(a, b)| arc_distance(s, a, b))
+                        .fold(T::infinity(), T::min),
+                    circumscribed_radius: edges
+                        .iter()
+                        .map(|&(a, _)| angle(s, a))
+                        .fold(T::zero(), T::max),
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,137 @@
+use core::cmp::Ordering;
+
+use d3_geo_rs::cartesian::cross;
+use d3_geo_rs::cartesian::dot;
+use geo::CoordFloat;
+use geo_types::Coord;
+
+use super::cartesian::cartesian;
+use super::Delaunay;
+
+/// The angle between two unit vectors.
+pub(super) fn angle<T: CoordFloat>(a: &[T; 3], b: &[T; 3]) -> T {
+    let c = cross(a, b);
+    dot(&c, &c).sqrt().atan2(dot(a, b))
+}
+
+/// The point of the great circle arc from `a` to `b`, the shorter one,
+/// closest to the unit vector `p`.
+pub(super) fn closest<T: CoordFloat>(
+    p: &[T; 3],
+    a: &[T; 3],
+    b: &[T; 3],
+) -> [T; 3] {
+    let n = cross(a, b);
+    let length = dot(&n, &n).sqrt();
+    if length > T::zero() {
+        let n = n.map(|x| x / length);
+        let h = dot(p, &n);
+        // The projection of p onto the great circle.
+        let q = [p[0] - h * n[0], p[1] - h * n[1], p[2] - h * n[2]];
+        let norm = dot(&q, &q).sqrt();
+        if norm > T::zero()
+            && dot(&cross(a, &q), &n) >= T::zero()
+            && dot(&cross(&q, b), &n) >= T::zero()
+        {
+            return q.map(|x| x / norm);
+        }
+    }
+    if angle(p, a) <= angle(p, b) {
+        *a
+    } else {
+        *b
+    }
+}
+
+/// The angle between the unit vector `p` and the great circle arc from
+/// `a` to `b`, the shorter one.
+pub(super) fn arc_distance<T: CoordFloat>(
+    p: &[T; 3],
+    a: &[T; 3],
+    b: &[T; 3],
+) -> T {
+    angle(p, &closest(p, a, b))
+}
+
+impl<T> Delaunay<T>
+where
+    T: CoordFloat,
+{
+    /// Returns the sites within `radius` ( in radians ) of `p`, in
+    /// degrees, with their distance to `p`, nearest first.
+    ///
+    /// The search floods the neighbors graph from the nearest site, as on
+    /// a Delaunay graph the sites inside a cap are connected.
+    #[must_use]
+    pub fn sites_within(&self, p: &Coord<T>, radius: T) -> Vec<(usize, T)> {
+        let xyz = cartesian(p);
+        let distance = |site: usize| angle(&xyz, &self.vectors[site]);
+        let mut within: Vec<(usize, T)> = self
+            .flood(p, |site| distance(site) < radius)
+            .into_iter()
+            .map(|site| (site, distance(site)))
+            .collect();
+        within.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+        within
+    }
+
+    /// Returns the sites whose cell intersects the cap of `radius`
+    /// ( in radians ) around `p`, in degrees, in increasing order.
+    ///
+    /// Empty when the cells were not computed, see [`super::Structures`].
+    #[must_use]
+    pub fn cells_within(&self, p: &Coord<T>, radius: T) -> Vec<usize> {
+        if self.polygons.is_empty() {
+            return Vec::new();
+        }
+        let xyz = cartesian(p);
+        let start = self.find(p, None);
+        let mut cells = self.flood(p, |site| {
+            // The cell of the nearest site holds p.
+            (Some(site) == start && radius > T::zero())
+                || self.cell_distance(&xyz, site) < radius
+        });
+        cells.sort_unstable();
+        cells
+    }
+
+    /// The angle between the unit vector `xyz` and the boundary of the
+    /// cell of `site`.
+    fn cell_distance(&self, xyz: &[T; 3], site: usize) -> T {
+        let ring: Vec<[T; 3]> = self.polygons[site]
+            .iter()
+            .map(|&c| cartesian(&self.centers[c]))
+            .collect();
+        (0..ring.len())
+            .map(|k| arc_distance(xyz, &ring[k], &ring[(k + 1) % ring.len()]))
+            .fold(T::infinity(), T::min)
+    }
+
+    /// The sites connected to the site nearest to `p` through accepted
+    /// sites, the nearest one included if accepted.
+    fn flood(
+        &self,
+        p: &Coord<T>,
+        accept: impl Fn(usize) -> bool,
+    ) -> Vec<usize> {
+        let Some(start) = self.find(p, None).filter(|&s| accept(s)) else {
+            return Vec::new();
+        };
+        let mut seen = vec![false; self.vectors.len()];
+        seen[start] = true;
+        let mut sites = vec![start];
+        let mut k = 0;
+        while k < sites.len() {
+            for &i in self.neighbors.get(&sites[k]).into_iter().flatten() {
+                if !seen[i] {
+                    seen[i] = true;
+                    if accept(i) {
+                        sites.push(i);
+                    }
+                }
+            }
+            k += 1;
+        }
+        sites
+    }
+}
@@ -0,0 +1,229 @@
+use core::fmt::Display;
+use core::fmt::Formatter;
+
+use geo::CoordFloat;
+use geo::Geometry;
+
+/// Geo-Voronoi construction error.
+///
+/// Explains why a set of sites could not be turned into a diagram.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ConstructionError {
+    /// The input geometry cannot be broken down into sites.
+    UnsupportedGeometry {
+        /// The name of the geometry type, as in "Polygon".
+        kind: &'static str,
+    },
+    /// Fewer sites than needed were supplied.
+    TooFewSites {
+        /// The number of valid sites.
+        got: usize,
+        /// The minimum number of valid sites.
+        needed: usize,
+    },
+    /// Every site has a non-finite coordinate.
+    AllSitesNonFinite,
+    /// Every site is at the same location.
+    AllSitesCoincident,
+    /// The weighted sites of a power diagram do not span a triangulation,
+    /// as when their apexes all lie on one plane.
+    ///
+    /// The sites of a [`crate::voronoi::Voronoi`] are always triangulated.
+    DegenerateTriangulation,
+}
+
+impl Display for ConstructionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnsupportedGeometry { kind } => {
+                write!(f, "cannot extract sites from a {kind}")
+            }
+            Self::TooFewSites { got, needed } => {
+                write!(f, "found {got} valid sites, needed at least {needed}")
+            }
+            Self::AllSitesNonFinite => {
+                write!(f, "every site has a non-finite coordinate")
+            }
+            Self::AllSitesCoincident => {
+                write!(f, "every site is at the same location")
+            }
+            Self::DegenerateTriangulation => {
+                write!(f, "the sites do not span a triangulation")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConstructionError {}
+
+/// Explains why a diagram of weighted sites could not be computed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum WeightError {
+    /// The number of weights differs from the number of sites.
+    CountMismatch {
+        /// The number of sites.
+        sites: usize,
+        /// The number of weights.
+        weights: usize,
+    },
+    /// A weight is out of range.
+    InvalidWeight {
+        /// The index of the site.
+        site: usize,
+    },
+    /// The weighted sites could not be turned into a diagram.
+    Construction(ConstructionError),
+}
+
+impl Display for WeightError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::CountMismatch { sites, weights } => {
+                write!(f, "found {weights} weights for {sites} sites")
+            }
+            Self::InvalidWeight { site } => {
+                write!(f, "the weight of site {site} is out of range")
+            }
+            Self::Construction(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for WeightError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Construction(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ConstructionError> for WeightError {
+    fn from(e: ConstructionError) -> Self {
+        Self::Construction(e)
+    }
+}
+
+/// Explains why values could not be interpolated over a diagram.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum InterpolationError {
+    /// There is not one value per site.
+    ValueCountMismatch {
+        /// The number of sites.
+        sites: usize,
+        /// The number of values.
+        values: usize,
+    },
+    /// There is not one value per input element.
+    InputCountMismatch {
+        /// The number of input elements.
+        inputs: usize,
+        /// The number of values.
+        values: usize,
+    },
+}
+
+impl Display for InterpolationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::ValueCountMismatch { sites, values } => {
+                write!(f, "found {values} values for {sites} sites")
+            }
+            Self::InputCountMismatch { inputs, values } => {
+                write!(f, "found {values} values for {inputs} input elements")
+            }
+        }
+    }
+}
+
+impl std::error::Error for InterpolationError {}
+
+/// Explains why a site could not be inserted into, or removed from, a
+/// diagram.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum UpdateError {
+    /// The inserted site has a non-finite coordinate.
+    NonFiniteSite,
+    /// The site does not exist, or was removed.
+    NoSuchSite {
+        /// The index of the site.
+        site: usize,
+    },
+    /// The triangulation had to be rebuilt, and the remaining sites could
+    /// not be triangulated.
+    Rebuild(ConstructionError),
+}
+
+impl Display for UpdateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NonFiniteSite => {
+                write!(f, "the site has a non-finite coordinate")
+            }
+            Self::NoSuchSite { site } => {
+                write!(f, "there is no site {site}")
+            }
+            Self::Rebuild(e) => write!(f, "cannot rebuild the diagram: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for UpdateError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Rebuild(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Explains why an input element was not given a site.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Rejection {
+    /// The feature has no geometry from which to extract a site.
+    MissingGeometry,
+    /// The site has a non-finite coordinate.
+    NonFinite,
+    /// The site was removed from the diagram.
+    Removed,
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MissingGeometry => write!(f, "the feature has no geometry"),
+            Self::NonFinite => {
+                write!(f, "the site has a non-finite coordinate")
+            }
+            Self::Removed => write!(f, "the site was removed"),
+        }
+    }
+}
+
+impl ConstructionError {
+    /// Returns the error reported for a geometry which cannot be broken
+    /// down into sites.
+    pub(crate) const fn unsupported<T>(geometry: &Geometry<T>) -> Self
+    where
+        T: CoordFloat,
+    {
+        let kind = match geometry {
+            Geometry::Point(_) => "Point",
+            Geometry::Line(_) => "Line",
+            Geometry::LineString(_) => "LineString",
+            Geometry::Polygon(_) => "Polygon",
+            Geometry::MultiPoint(_) => "MultiPoint",
+            Geometry::MultiLineString(_) => "MultiLineString",
+            Geometry::MultiPolygon(_) => "MultiPolygon",
+            Geometry::GeometryCollection(_) => "GeometryCollection",
+            Geometry::Rect(_) => "Rect",
+            Geometry::Triangle(_) => "Triangle",
+        };
+        Self::UnsupportedGeometry { kind }
+    }
+}
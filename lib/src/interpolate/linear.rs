@@ -0,0 +1,147 @@
+#![allow(clippy::many_single_char_names)]
+use geo::CoordFloat;
+use geo_types::Coord;
+
+use crate::error::InterpolationError;
+use crate::voronoi::Voronoi;
+
+use super::per_input;
+use super::per_site;
+
+/// Piecewise linear interpolation of values given at the sites of a
+/// [`Voronoi`] diagram, over the triangles of its Delaunay triangulation.
+///
+/// Within a triangle, the value is the average of the values at its
+/// vertices weighted by the barycentric coordinates returned by
+/// [`crate::delaunay::Delaunay::locate`].
+pub struct Linear<'a, T>
+where
+    T: CoordFloat,
+{
+    voronoi: &'a Voronoi<T>,
+    values: Vec<T>,
+}
+
+impl<T> core::fmt::Debug for Linear<'_, T>
+where
+    T: CoordFloat,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Linear")
+            .field("values", &self.values)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T> Linear<'a, T>
+where
+    T: CoordFloat,
+{
+    /// Prepares the interpolation of one value per site of the diagram,
+    /// in the order of `voronoi.delaunay.points`.
+    ///
+    /// See [`Self::from_inputs`] for one value per input element.
+    ///
+    /// # Errors
+    ///  When there is not one value per site.
+    pub fn try_new(
+        voronoi: &'a Voronoi<T>,
+        values: &[T],
+    ) -> Result<Self, InterpolationError> {
+        Ok(Self {
+            voronoi,
+            values: per_site(voronoi, values)?,
+        })
+    }
+
+    /// Prepares the interpolation of one value per input element of the
+    /// diagram, see [`Voronoi::index`].
+    ///
+    /// The values of the elements merged into one site are averaged, those
+    /// of the elements without a site are ignored.
+    ///
+    /// # Errors
+    ///  When there is not one value per input element.
+    pub fn from_inputs(
+        voronoi: &'a Voronoi<T>,
+        values: &[T],
+    ) -> Result<Self, InterpolationError> {
+        Ok(Self {
+            voronoi,
+            values: per_input(voronoi, values)?,
+        })
+    }
+
+    /// Returns the interpolated value at `p`, in degrees.
+    ///
+    /// Returns None when `p` is not finite, or lies outside the triangles,
+    /// which only happens when the sites lie in a hemisphere.
+    #[must_use]
+    pub fn value(&self, p: &Coord<T>) -> Option<T> {
+        self.value_from(p, &mut None)
+    }
+
+    /// Returns the interpolated value at each point, as
+    /// [`Linear::value`].
+    ///
+    /// Each search starts from the triangle of the previous point, which
+    /// is fast when consecutive points are close.
+    #[must_use]
+    pub fn values(&self, points: &[Coord<T>]) -> Vec<Option<T>> {
+        let mut hint = None;
+        points
+            .iter()
+            .map(|p| self.value_from(p, &mut hint))
+            .collect()
+    }
+
+    /// Returns the interpolated values at the centers of the cells of an
+    /// equirectangular grid covering the sphere, `width` cells from west
+    /// to east and `height` cells from north to south.
+    ///
+    /// The values are in row order, starting from the north west corner.
+    #[must_use]
+    pub fn grid(&self, width: usize, height: usize) -> Vec<Option<T>> {
+        let (Some(w), Some(h), Some(half_turn)) =
+            (T::from(width), T::from(height), T::from(180))
+        else {
+            return Vec::new();
+        };
+        let half = T::one() / (T::one() + T::one());
+        let (x_step, y_step) = ((half_turn + half_turn) / w, half_turn / h);
+        let mut values = Vec::with_capacity(width * height);
+        // Each row starts from the triangle found at the start of the
+        // previous one.
+        let mut row_hint = None;
+        let mut j = T::zero();
+        for _ in 0..height {
+            let y = half_turn * half - (j + half) * y_step;
+            let mut hint = row_hint;
+            let mut i = T::zero();
+            for column in 0..width {
+                let x = (i + half) * x_step - half_turn;
+                values.push(self.value_from(&Coord { x, y }, &mut hint));
+                if column == 0 {
+                    row_hint = hint;
+                }
+                i = i + T::one();
+            }
+            j = j + T::one();
+        }
+        values
+    }
+
+    /// The value at `p`, walking from the triangle `hint`, which is
+    /// updated to the triangle holding `p`.
+    fn value_from(&self, p: &Coord<T>, hint: &mut Option<usize>) -> Option<T> {
+        let delaunay = &self.voronoi.delaunay;
+        let (t, weights) = delaunay.locate_from(p, *hint)?;
+        *hint = Some(t);
+        let tri = delaunay.triangles[t];
+        Some(
+            (0..3).fold(T::zero(), |sum, k| {
+                sum + weights[k] * self.values[tri[k]]
+            }),
+        )
+    }
+}
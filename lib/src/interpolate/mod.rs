@@ -0,0 +1,65 @@
+mod linear;
+mod sibson;
+
+use geo::CoordFloat;
+
+use crate::error::InterpolationError;
+use crate::voronoi::Voronoi;
+
+pub use linear::Linear;
+pub use sibson::Sibson;
+
+/// Checks that there is one value per site of the diagram.
+fn per_site<T>(
+    voronoi: &Voronoi<T>,
+    values: &[T],
+) -> Result<Vec<T>, InterpolationError>
+where
+    T: CoordFloat,
+{
+    let sites = voronoi.delaunay.points.len();
+    if values.len() == sites {
+        Ok(values.to_vec())
+    } else {
+        Err(InterpolationError::ValueCountMismatch {
+            sites,
+            values: values.len(),
+        })
+    }
+}
+
+/// Collapses one value per input element of the diagram into one value per
+/// site, averaging the values of the elements merged into a site.
+///
+/// The values of the elements without a site are ignored, and a site
+/// without an element, as a removed site, is given NaN.
+fn per_input<T>(
+    voronoi: &Voronoi<T>,
+    values: &[T],
+) -> Result<Vec<T>, InterpolationError>
+where
+    T: CoordFloat,
+{
+    let index = voronoi.index();
+    if values.len() != index.len() {
+        return Err(InterpolationError::InputCountMismatch {
+            inputs: index.len(),
+            values: values.len(),
+        });
+    }
+    let mut sums = vec![(T::zero(), 0usize); voronoi.delaunay.points.len()];
+    for (value, site) in values.iter().zip(index) {
+        if let Some(site) = site {
+            sums[*site].0 = sums[*site].0 + *value;
+            sums[*site].1 += 1;
+        }
+    }
+    Ok(sums
+        .into_iter()
+        .map(|(sum, n)| {
+            T::from(n)
+                .filter(|n| *n > T::zero())
+                .map_or_else(T::nan, |n| sum / n)
+        })
+        .collect())
+}
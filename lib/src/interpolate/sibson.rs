@@ -0,0 +1,209 @@
+#![allow(clippy::many_single_char_names)]
+use std::collections::HashMap;
+
+use d3_geo_rs::cartesian::dot;
+use geo::CoordFloat;
+use geo_types::Coord;
+
+use crate::delaunay::cartesian::cartesian;
+use crate::delaunay::circumcenters::circumcenter;
+use crate::delaunay::excess::area_cartesian;
+use crate::error::InterpolationError;
+use crate::voronoi::Voronoi;
+
+use super::per_input;
+use super::per_site;
+
+/// Natural neighbor ( Sibson ) interpolation of values given at the sites
+/// of a [`Voronoi`] diagram.
+///
+/// Inserting a point in the diagram gives it a cell, made of parts of the
+/// cells of its natural neighbors. The natural neighbor coordinates of the
+/// point are the fractions of its cell taken from each neighbor, and the
+/// interpolated value is the average of the neighbors' values weighted by
+/// these coordinates. The diagram itself is left unchanged.
+///
+/// The interpolant is continuous, equals the values at the sites, and
+/// only depends on the sites around the point.
+pub struct Sibson<'a, T>
+where
+    T: CoordFloat,
+{
+    voronoi: &'a Voronoi<T>,
+    values: Vec<T>,
+    // The triangle holding each directed edge.
+    triangle_of: HashMap<(usize, usize), usize>,
+}
+
+impl<T> core::fmt::Debug for Sibson<'_, T>
+where
+    T: CoordFloat,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Sibson")
+            .field("values", &self.values)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T> Sibson<'a, T>
+where
+    T: CoordFloat,
+{
+    /// Prepares the interpolation of one value per site of the diagram,
+    /// in the order of `voronoi.delaunay.points`.
+    ///
+    /// See [`Self::from_inputs`] for one value per input element.
+    ///
+    /// # Errors
+    ///  When there is not one value per site.
+    pub fn try_new(
+        voronoi: &'a Voronoi<T>,
+        values: &[T],
+    ) -> Result<Self, InterpolationError> {
+        Ok(Self::new(voronoi, per_site(voronoi, values)?))
+    }
+
+    /// Prepares the interpolation of one value per input element of the
+    /// diagram, see [`Voronoi::index`].
+    ///
+    /// The values of the elements merged into one site are averaged, those
+    /// of the elements without a site are ignored.
+    ///
+    /// # Errors
+    ///  When there is not one value per input element.
+    pub fn from_inputs(
+        voronoi: &'a Voronoi<T>,
+        values: &[T],
+    ) -> Result<Self, InterpolationError> {
+        Ok(Self::new(voronoi, per_input(voronoi, values)?))
+    }
+
+    fn new(voronoi: &'a Voronoi<T>, values: Vec<T>) -> Self {
+        let mut triangle_of = HashMap::new();
+        for (t, tri) in voronoi.delaunay.triangles.iter().enumerate() {
+            for j in 0..3 {
+                triangle_of.insert((tri[j], tri[(j + 1) % 3]), t);
+            }
+        }
+        Self {
+            voronoi,
+            values,
+            triangle_of,
+        }
+    }
+
+    /// Returns the natural neighbor coordinates of `p`, in degrees: the
+    /// natural neighbors of `p`, in increasing order, each with its
+    /// weight. The weights are positive and sum to one.
+    ///
+    /// Returns None when `p` is not finite, or when the cell of `p` would
+    /// not be bounded by the triangles, which only happens when the sites
+    /// lie in a hemisphere.
+    #[must_use]
+    pub fn coordinates(&self, p: &Coord<T>) -> Option<Vec<(usize, T)>> {
+        let delaunay = &self.voronoi.delaunay;
+        let (start, _) = delaunay.locate(p)?;
+        let xyz = cartesian(p);
+        let vectors = &delaunay.vectors;
+        let triangles = &delaunay.triangles;
+        let center = |tri: [usize; 3]| circumcenter(&tri.map(|i| vectors[i]));
+
+        let tolerance = T::epsilon() * (T::one() + T::one()).powi(6);
+        if let Some(&site) = triangles[start].iter().find(|&&i| {
+            let v = vectors[i];
+            dot(&v, &xyz) >= T::one() - tolerance
+        }) {
+            return Some(vec![(site, T::one())]);
+        }
+
+        // The triangles whose circumcircle holds p.
+        let in_circle = |t: usize| {
+            let c = center(triangles[t]);
+            dot(&c, &xyz) > dot(&c, &vectors[triangles[t][0]])
+        };
+        let mut cavity = vec![start];
+        let mut k = 0;
+        while k < cavity.len() {
+            let tri = triangles[cavity[k]];
+            for j in 0..3 {
+                let u = *self.triangle_of.get(&(tri[(j + 1) % 3], tri[j]))?;
+                if !cavity.contains(&u) && in_circle(u) {
+                    cavity.push(u);
+                }
+            }
+            k += 1;
+        }
+        // The boundary of the cavity, each natural neighbor with the
+        // neighbors before and after it.
+        let mut after = HashMap::new();
+        let mut before = HashMap::new();
+        for &t in &cavity {
+            let tri = triangles[t];
+            for j in 0..3 {
+                let (a, b) = (tri[j], tri[(j + 1) % 3]);
+                if !cavity.contains(&self.triangle_of[&(b, a)]) {
+                    after.insert(a, b);
+                    before.insert(b, a);
+                }
+            }
+        }
+
+        // The part of the cell of p taken from the cell of a is bounded by
+        // the new edge between p and a, and the old edges of a within the
+        // cavity.
+        let new_center =
+            |a: usize, b: usize| circumcenter(&[vectors[a], vectors[b], xyz]);
+        let mut coordinates = Vec::with_capacity(after.len());
+        let mut total = T::zero();
+        for (&a, &y) in &after {
+            let x = *before.get(&a)?;
+            let mut ring = vec![new_center(x, a)];
+            let mut t = self.triangle_of[&(x, a)];
+            for _ in 0..cavity.len() {
+                ring.push(center(triangles[t]));
+                let tri = triangles[t];
+                let j = tri.iter().position(|&i| i == a)?;
+                let next = tri[(j + 1) % 3];
+                if next == y {
+                    break;
+                }
+                t = self.triangle_of[&(next, a)];
+            }
+            ring.push(new_center(a, y));
+            let area = (1..ring.len() - 1)
+                .map(|k| area_cartesian(&[ring[0], ring[k], ring[k + 1]]))
+                .fold(T::zero(), |sum, area| sum + area)
+                .abs();
+            total = total + area;
+            coordinates.push((a, area));
+        }
+        if total <= T::zero() {
+            return None;
+        }
+        for c in &mut coordinates {
+            c.1 = c.1 / total;
+        }
+        coordinates.sort_unstable_by_key(|c| c.0);
+        Some(coordinates)
+    }
+
+    /// Returns the interpolated value at `p`, in degrees.
+    ///
+    /// Returns None where [`Sibson::coordinates`] does.
+    #[must_use]
+    pub fn value(&self, p: &Coord<T>) -> Option<T> {
+        self.coordinates(p).map(|coordinates| {
+            coordinates
+                .iter()
+                .fold(T::zero(), |sum, &(site, w)| sum + w * self.values[site])
+        })
+    }
+
+    /// Returns the interpolated value at each point, as
+    /// [`Sibson::value`].
+    #[must_use]
+    pub fn values(&self, points: &[Coord<T>]) -> Vec<Option<T>> {
+        points.iter().map(|p| self.value(p)).collect()
+    }
+}
@@ -18,10 +18,24 @@ extern crate d3_geo_rs;
 /// Allows debug to be auto-derived from complex structs.
 extern crate float_next_after;
 extern crate geo;
+/// Voronoi diagrams of sites with a radius.
+pub mod apollonius;
+
 /// Delaunay helper functions.
 pub mod delaunay;
 
+/// Errors reported when constructing a diagram.
+pub mod error;
+
+/// Interpolation of values given at the sites of a diagram.
+pub mod interpolate;
+
+/// Spherical power diagrams, the Voronoi diagrams of weighted sites.
+pub mod power;
+
 /// Wrapper for Delaunay contains helper methods.
 pub mod voronoi;
 
+mod convex_hull;
 mod extent;
+mod queue;
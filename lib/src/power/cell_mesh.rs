@@ -0,0 +1,32 @@
+use geo::line_string;
+use geo::CoordFloat;
+use geo::LineString;
+use geo::MultiLineString;
+
+use super::PowerDiagram;
+
+impl<T> PowerDiagram<T>
+where
+    T: CoordFloat,
+{
+    /// Returns every cell edge once, as `Voronoi::cell_mesh()`.
+    #[must_use]
+    pub fn cell_mesh(&self) -> MultiLineString<T> {
+        let mut coordinates: Vec<LineString<T>> =
+            Vec::with_capacity(self.edges.len());
+        for p in &self.polygons {
+            let Some(last) = p.last() else {
+                continue;
+            };
+            let mut p0 = *last;
+            for &p1 in p {
+                if p1 > p0 {
+                    coordinates
+                        .push(line_string![self.centers[p0], self.centers[p1]]);
+                }
+                p0 = p1;
+            }
+        }
+        MultiLineString(coordinates)
+    }
+}
@@ -0,0 +1,40 @@
+use geo::CoordFloat;
+use geo::Geometry;
+use geo::LineString;
+
+use d3_geo_rs::data_object::FeatureCollection;
+use d3_geo_rs::data_object::FeatureProperty;
+use d3_geo_rs::data_object::Features;
+use d3_geo_rs::distance::distance;
+
+use super::PowerDiagram;
+
+impl<T> PowerDiagram<T>
+where
+    T: CoordFloat,
+{
+    /// Returns the edges of the regular triangulation, labelled with
+    /// their source, target and length in radians.
+    ///
+    /// As `Voronoi::links()`, without the `Urquhart` property.
+    #[must_use]
+    pub fn links(&self) -> FeatureCollection<T> {
+        let features = self
+            .edges
+            .iter()
+            .map(|&(a, b)| {
+                let (a, b) = (self.points[a], self.points[b]);
+                let ls: LineString<T> = vec![a, b].into();
+                Features {
+                    properties: vec![
+                        FeatureProperty::Source(a),
+                        FeatureProperty::Target(b),
+                        FeatureProperty::Length(distance(&a, &b)),
+                    ],
+                    geometry: vec![Geometry::LineString(ls)],
+                }
+            })
+            .collect();
+        FeatureCollection(features)
+    }
+}
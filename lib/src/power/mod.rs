@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+
+use d3_geo_rs::cartesian::cross;
+use d3_geo_rs::cartesian::dot;
+use d3_geo_rs::cartesian::normalize;
+use d3_geo_rs::cartesian::spherical;
+use geo::CoordFloat;
+use geo_types::Coord;
+use num_traits::FloatConst;
+
+use crate::convex_hull::convex_hull;
+use crate::delaunay::cartesian::cartesian;
+use crate::error::ConstructionError;
+use crate::error::Rejection;
+use crate::error::WeightError;
+
+mod cell_mesh;
+mod links;
+mod polygons;
+mod triangles;
+
+/// A power diagram on the sphere, the Voronoi diagram of weighted sites.
+///
+/// The weight of a site is the radius of a spherical cap centered on the
+/// site, in radians. A point belongs to the cell of the site whose cap
+/// it is closest to in power, that is maximizing `cos(d) / cos(r)` where
+/// `d` is the distance to the site and `r` the radius of its cap. A site
+/// with a larger weight claims more territory, and may claim all of its
+/// neighbor's cell, in which case the neighbor's cell vanishes.
+///
+/// The cells are bounded by great circle arcs. With equal weights the
+/// diagram is the unweighted Voronoi diagram.
+///
+/// ```
+/// use geo_types::Coord;
+/// use d3_geo_voronoi_rs::power::PowerDiagram;
+///
+/// let sites = [
+///     Coord { x: 0f64, y: 0f64 },
+///     Coord { x: 90f64, y: 0f64 },
+///     Coord { x: 180f64, y: 0f64 },
+///     Coord { x: -90f64, y: 0f64 },
+///     Coord { x: 0f64, y: 90f64 },
+///     Coord { x: 0f64, y: -90f64 },
+/// ];
+/// let weights = [0.5f64, 0.1, 0.1, 0.1, 0.1, 0.1];
+/// let power = PowerDiagram::try_new(&sites, &weights).unwrap();
+/// assert_eq!(power.polygons.len(), 6);
+/// assert!(power.vanished().is_empty());
+/// ```
+#[derive(Clone, Debug)]
+pub struct PowerDiagram<T>
+where
+    T: CoordFloat,
+{
+    /// The sites, in input order.
+    pub points: Vec<Coord<T>>,
+    /// The weight of each site.
+    pub weights: Vec<T>,
+    /// The regular ( weighted Delaunay ) triangulation, with the
+    /// orientation of `Delaunay::triangles`.
+    pub triangles: Vec<[usize; 3]>,
+    /// The power vertex of each triangle, shared by the cells of its three
+    /// sites.
+    pub centers: Vec<Coord<T>>,
+    /// The cell of each site, as a ring of indices into `centers`.
+    ///
+    /// Empty when the site was rejected or its cell vanished.
+    pub polygons: Vec<Vec<usize>>,
+    /// The sites sharing a cell edge with each site.
+    pub neighbors: Vec<Vec<usize>>,
+    /// The edges of the regular triangulation, lower index first.
+    pub edges: Vec<(usize, usize)>,
+    rejected: Vec<(usize, Rejection)>,
+    vanished: Vec<usize>,
+}
+
+/// The power vertex of a face of the hull of the apexes, the direction of
+/// its outward normal.
+///
+/// None for a sliver face, between near coincident apexes, which has no
+/// normal.
+fn center<T: CoordFloat + FloatConst>(
+    [a, b, c]: [[T; 3]; 3],
+) -> Option<Coord<T>> {
+    let ab = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+    let ac = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+    let normal = cross(&ab, &ac);
+    let norm2 = dot(&normal, &normal);
+    (norm2 > T::zero() && norm2.is_finite())
+        .then(|| spherical(&normalize(&normal)))
+}
+
+impl<T> PowerDiagram<T>
+where
+    T: CoordFloat + FloatConst,
+{
+    /// Computes the power diagram of the sites, one weight per site.
+    ///
+    /// Weights are cap radii in radians, in `[0, PI / 2)`. Sites with a
+    /// non-finite coordinate are rejected, see [`PowerDiagram::rejected`].
+    ///
+    /// Runs in expected O(n log n) time, the cost of the convex hull of
+    /// the lifted sites.
+    ///
+    /// # Errors
+    ///  When the weights do not match the sites, when a weight is out of
+    ///  range, when fewer than four sites are valid, or when the weighted
+    ///  sites do not span a triangulation.
+    pub fn try_new(
+        sites: &[Coord<T>],
+        weights: &[T],
+    ) -> Result<Self, WeightError> {
+        if sites.len() != weights.len() {
+            return Err(WeightError::CountMismatch {
+                sites: sites.len(),
+                weights: weights.len(),
+            });
+        }
+        if let Some(site) = weights
+            .iter()
+            .position(|w| !(*w >= T::zero() && *w < T::FRAC_PI_2()))
+        {
+            return Err(WeightError::InvalidWeight { site });
+        }
+
+        let mut rejected = Vec::new();
+        // Each site is lifted to its cap's apex, the cells are then the
+        // normal fan of the convex hull of the apexes.
+        let apexes: Vec<[T; 3]> = sites
+            .iter()
+            .zip(weights)
+            .enumerate()
+            .map(|(i, (site, weight))| {
+                if (site.x + site.y).is_finite() {
+                    let c = cartesian(site);
+                    let k = weight.cos();
+                    [c[0] / k, c[1] / k, c[2] / k]
+                } else {
+                    rejected.push((i, Rejection::NonFinite));
+                    [T::nan(); 3]
+                }
+            })
+            .collect();
+
+        let got = sites.len() - rejected.len();
+        if got == 0 && !sites.is_empty() {
+            return Err(ConstructionError::AllSitesNonFinite.into());
+        }
+        if got < 4 {
+            return Err(
+                ConstructionError::TooFewSites { got, needed: 4 }.into()
+            );
+        }
+        let faces = convex_hull(&apexes)
+            .ok_or(ConstructionError::DegenerateTriangulation)?;
+
+        let centers: Vec<Coord<T>> = faces
+            .iter()
+            .map(|f| center(f.map(|i| apexes[i])))
+            .collect::<Option<_>>()
+            .ok_or(ConstructionError::DegenerateTriangulation)?;
+
+        // Walk the faces around each vertex. The hull faces are
+        // counterclockwise seen from outside, the cells are reported
+        // clockwise, as are the triangles.
+        let mut face_of: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut first_face: Vec<Option<usize>> = vec![None; sites.len()];
+        for (f, face) in faces.iter().enumerate() {
+            for j in 0..3 {
+                face_of.insert((face[j], face[(j + 1) % 3]), f);
+                first_face[face[j]].get_or_insert(f);
+            }
+        }
+        let mut polygons = vec![Vec::new(); sites.len()];
+        let mut neighbors = vec![Vec::new(); sites.len()];
+        let mut vanished = Vec::new();
+        for (i, first) in first_face.iter().enumerate() {
+            let Some(first) = *first else {
+                if apexes[i][0].is_finite() {
+                    vanished.push(i);
+                }
+                continue;
+            };
+            let mut f = first;
+            for _ in 0..faces.len() {
+                let face = faces[f];
+                let j = face.iter().position(|&v| v == i).unwrap_or(0);
+                let next = face[(j + 2) % 3];
+                polygons[i].push(f);
+                neighbors[i].push(next);
+                match face_of.get(&(i, next)) {
+                    Some(&g) if g != first => f = g,
+                    _ => break,
+                }
+            }
+            polygons[i].reverse();
+            neighbors[i].reverse();
+        }
+
+        let mut edges: Vec<(usize, usize)> =
+            face_of.keys().filter(|(a, b)| a < b).copied().collect();
+        edges.sort_unstable();
+
+        Ok(Self {
+            points: sites.to_vec(),
+            weights: weights.to_vec(),
+            triangles: faces.iter().map(|f| [f[0], f[2], f[1]]).collect(),
+            centers,
+            polygons,
+            neighbors,
+            edges,
+            rejected,
+            vanished,
+        })
+    }
+}
+
+impl<T> PowerDiagram<T>
+where
+    T: CoordFloat,
+{
+    /// The sites which were rejected, with the reason for each.
+    #[must_use]
+    pub fn rejected(&self) -> &[(usize, Rejection)] {
+        &self.rejected
+    }
+
+    /// The valid sites without a cell: claimed by the cells of sites with
+    /// larger weights, shrunk to a point where the cells of other sites
+    /// meet, or sharing their location and weight with another site, which
+    /// keeps the cell.
+    #[must_use]
+    pub fn vanished(&self) -> &[usize] {
+        &self.vanished
+    }
+}
@@ -0,0 +1,48 @@
+use geo::CoordFloat;
+use geo::Geometry;
+use geo::LineString;
+use geo::Polygon;
+
+use d3_geo_rs::data_object::FeatureCollection;
+use d3_geo_rs::data_object::FeatureProperty;
+use d3_geo_rs::data_object::Features;
+
+use super::PowerDiagram;
+
+impl<T> PowerDiagram<T>
+where
+    T: CoordFloat,
+{
+    /// Returns the cells in the form of a feature collection, as
+    /// `Voronoi::polygons()`.
+    ///
+    /// The features are ordered by site, a rejected site or a vanished
+    /// cell has an empty polygon.
+    #[must_use]
+    pub fn polygons(&self) -> FeatureCollection<T> {
+        let features = self
+            .polygons
+            .iter()
+            .enumerate()
+            .map(|(i, poly)| {
+                let exterior: LineString<T> = poly
+                    .iter()
+                    .chain(poly.first())
+                    .map(|&c| self.centers[c])
+                    .collect();
+                Features {
+                    geometry: vec![Geometry::Polygon(Polygon::new(
+                        exterior,
+                        vec![],
+                    ))],
+                    properties: vec![
+                        FeatureProperty::Site(self.points[i]),
+                        FeatureProperty::Sitecoordinates(self.points[i]),
+                        FeatureProperty::Neighbors(self.neighbors[i].clone()),
+                    ],
+                }
+            })
+            .collect();
+        FeatureCollection(features)
+    }
+}
@@ -0,0 +1,41 @@
+use geo::CoordFloat;
+use geo::Geometry;
+use geo::LineString;
+use geo::Polygon;
+
+use d3_geo_rs::data_object::FeatureCollection;
+use d3_geo_rs::data_object::FeatureProperty;
+use d3_geo_rs::data_object::Features;
+
+use super::PowerDiagram;
+
+impl<T> PowerDiagram<T>
+where
+    T: CoordFloat,
+{
+    /// Returns the regular triangulation, each triangle labelled with its
+    /// power vertex, as `Voronoi::triangles()`.
+    #[must_use]
+    pub fn triangles(&self) -> FeatureCollection<T> {
+        let features = self
+            .triangles
+            .iter()
+            .zip(&self.centers)
+            .map(|(tri, center)| {
+                let ring: LineString<T> = tri
+                    .iter()
+                    .chain(tri.first())
+                    .map(|&i| self.points[i])
+                    .collect();
+                Features {
+                    properties: vec![FeatureProperty::Circumecenter(*center)],
+                    geometry: vec![Geometry::Polygon(Polygon::new(
+                        ring,
+                        vec![],
+                    ))],
+                }
+            })
+            .collect();
+        FeatureCollection(features)
+    }
+}
@@ -0,0 +1,39 @@
+use core::cmp::Ordering;
+
+use geo::CoordFloat;
+
+/// A node waiting in a priority queue, with the cost of the path to it
+/// and its priority.
+///
+/// Ordered in reverse, so that a `BinaryHeap` pops the lowest priority
+/// first.
+#[derive(Debug)]
+pub struct Queued<T> {
+    pub priority: T,
+    pub cost: T,
+    pub node: usize,
+}
+
+impl<T: CoordFloat> PartialEq for Queued<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: CoordFloat> Eq for Queued<T> {}
+
+impl<T: CoordFloat> PartialOrd for Queued<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: CoordFloat> Ord for Queued<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .priority
+            .partial_cmp(&self.priority)
+            .unwrap_or(Ordering::Equal)
+            .then(other.node.cmp(&self.node))
+    }
+}
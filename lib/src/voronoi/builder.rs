@@ -0,0 +1,274 @@
+use core::fmt::Debug;
+use std::rc::Rc;
+
+use float_next_after::NextAfter;
+use geo::centroid::Centroid;
+use geo::CoordFloat;
+use geo::GeoFloat;
+use geo::GeoNum;
+use geo::Geometry;
+use geo::Point;
+use geo_types::Coord;
+use num_traits::Bounded;
+use num_traits::FloatConst;
+use num_traits::FromPrimitive;
+use num_traits::Signed;
+
+use d3_geo_rs::data_object::Features;
+
+use crate::delaunay::Structures;
+
+use super::ConstructionError;
+use super::VTransform;
+use super::Voronoi;
+
+/// The angular unit of the sites returned by the accessors.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Units {
+    /// Longitude and latitude in degrees.
+    #[default]
+    Degrees,
+    /// Longitude and latitude in radians.
+    Radians,
+}
+
+/// Collects the options used to construct a [`Voronoi`] diagram.
+///
+/// ```
+/// use geo::Geometry;
+/// use geo::MultiPoint;
+/// use geo::Point;
+/// use d3_geo_voronoi_rs::voronoi::Voronoi;
+///
+/// let sites = MultiPoint(vec![
+///     Point::new(0f64, 0f64),
+///     Point::new(10f64, 0f64),
+///     Point::new(0f64, 10f64),
+/// ]);
+///
+/// let mut builder = Voronoi::builder();
+/// builder.radius_set(6371_f64);
+/// let voronoi = builder.build(Geometry::MultiPoint(sites));
+/// assert!(voronoi.is_ok());
+/// ```
+pub struct VoronoiBuilder<T>
+where
+    T: CoordFloat,
+{
+    pub(super) vx: VTransform<T>,
+    pub(super) vy: VTransform<T>,
+    units: Units,
+    radius: T,
+    tolerance: T,
+    structures: Structures,
+}
+
+impl<T> Clone for VoronoiBuilder<T>
+where
+    T: CoordFloat,
+{
+    fn clone(&self) -> Self {
+        Self {
+            vx: self.vx.clone(),
+            vy: self.vy.clone(),
+            units: self.units,
+            radius: self.radius,
+            tolerance: self.tolerance,
+            structures: self.structures,
+        }
+    }
+}
+
+impl<T> Debug for VoronoiBuilder<T>
+where
+    T: CoordFloat,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("VoronoiBuilder")
+            .field("units", &self.units)
+            .field("radius", &self.radius)
+            .field("tolerance", &self.tolerance)
+            .field("structures", &self.structures)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Default for VoronoiBuilder<T>
+where
+    T: 'static + CoordFloat + GeoFloat,
+{
+    fn default() -> Self {
+        Self {
+            vx: Rc::new(|d: &Geometry<T>| {
+                d.centroid().map_or_else(T::nan, Point::x)
+            }),
+            vy: Rc::new(|d: &Geometry<T>| {
+                d.centroid().map_or_else(T::nan, Point::y)
+            }),
+            units: Units::default(),
+            radius: T::one(),
+            tolerance: T::zero(),
+            structures: Structures::default(),
+        }
+    }
+}
+
+impl<T> VoronoiBuilder<T>
+where
+    T: CoordFloat,
+{
+    /// Returns the `x()` accessor.
+    pub fn x(&self) -> &VTransform<T> {
+        &self.vx
+    }
+
+    /// Sets the `x()` accessor, which maps each element of the input onto
+    /// the longitude of its site.
+    pub fn x_set(
+        &mut self,
+        f: impl Fn(&Geometry<T>) -> T + 'static,
+    ) -> &mut Self {
+        self.vx = Rc::new(f);
+        self
+    }
+
+    /// Returns the `y()` accessor.
+    pub fn y(&self) -> &VTransform<T> {
+        &self.vy
+    }
+
+    /// Sets the `y()` accessor, which maps each element of the input onto
+    /// the latitude of its site.
+    pub fn y_set(
+        &mut self,
+        f: impl Fn(&Geometry<T>) -> T + 'static,
+    ) -> &mut Self {
+        self.vy = Rc::new(f);
+        self
+    }
+
+    /// Returns the units of the accessors output.
+    pub const fn units(&self) -> Units {
+        self.units
+    }
+
+    /// Sets the units of the accessors output.
+    ///
+    /// Sites are converted to degrees before the diagram is computed.
+    pub const fn units_set(&mut self, units: Units) -> &mut Self {
+        self.units = units;
+        self
+    }
+
+    /// Returns the sphere radius.
+    pub const fn radius(&self) -> T {
+        self.radius
+    }
+
+    /// Sets the sphere radius, defaults to 1.
+    ///
+    /// Distances reported by `links()` and the cutoff given to `find()`
+    /// are measured on a sphere of this radius.
+    pub const fn radius_set(&mut self, radius: T) -> &mut Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Returns the distance below which sites are merged.
+    pub const fn tolerance(&self) -> T {
+        self.tolerance
+    }
+
+    /// Sets the distance below which sites are merged, measured on a
+    /// sphere of the configured radius. Defaults to 0, which merges only
+    /// sites with identical coordinates.
+    ///
+    /// Merged sites share one cell, represented by the first of them.
+    pub const fn tolerance_set(&mut self, tolerance: T) -> &mut Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// The tolerance as an angle in radians.
+    pub(super) fn angular_tolerance(&self) -> T {
+        self.tolerance / self.radius
+    }
+
+    /// Returns the selection of derived structures to compute.
+    pub const fn structures(&self) -> Structures {
+        self.structures
+    }
+
+    /// Selects the derived structures to compute, defaults to all.
+    pub const fn structures_set(
+        &mut self,
+        structures: Structures,
+    ) -> &mut Self {
+        self.structures = structures;
+        self
+    }
+}
+
+impl<T> VoronoiBuilder<T>
+where
+    T: 'static
+        + Bounded
+        + CoordFloat
+        + Default
+        + FloatConst
+        + FromPrimitive
+        + GeoNum
+        + NextAfter
+        + Signed,
+{
+    /// Computes the diagram, one site per element of the input.
+    ///
+    /// # Errors
+    ///  See [`Voronoi::with_data`].
+    pub fn build(
+        &self,
+        data: Geometry<T>,
+    ) -> Result<Voronoi<T>, ConstructionError> {
+        self.voronoi().with_data(data)
+    }
+
+    /// Computes the diagram, one site per feature.
+    ///
+    /// # Errors
+    ///  See [`Voronoi::with_features`].
+    pub fn build_from_features(
+        &self,
+        features: Vec<Features<T>>,
+    ) -> Result<Voronoi<T>, ConstructionError> {
+        self.voronoi().with_features(features)
+    }
+
+    /// Computes the diagram, one site per coordinate.
+    ///
+    /// # Errors
+    ///  See [`Voronoi::with_coords`].
+    pub fn build_from_coords(
+        &self,
+        coords: &[Coord<T>],
+    ) -> Result<Voronoi<T>, ConstructionError> {
+        self.voronoi().with_coords(coords)
+    }
+
+    /// Computes the diagram, one site per 3D cartesian vector.
+    ///
+    /// # Errors
+    ///  See [`Voronoi::with_unit_vectors`].
+    pub fn build_from_unit_vectors(
+        &self,
+        vectors: &[[T; 3]],
+    ) -> Result<Voronoi<T>, ConstructionError> {
+        self.voronoi().with_unit_vectors(vectors)
+    }
+
+    fn voronoi(&self) -> Voronoi<T> {
+        Voronoi {
+            config: self.clone(),
+            ..Voronoi::default()
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use core::fmt::Debug;
+
+use geo::CoordFloat;
+use geo::LineString;
+use geo::Polygon;
+use geo_types::Coord;
+use num_traits::FloatConst;
+
+use d3_geo_rs::data_object::Features;
+
+use super::Voronoi;
+
+/// A view of the cell of one site, its geometry read from the diagram on
+/// demand.
+///
+/// See [`Voronoi::cells`].
+#[derive(Clone, Copy)]
+pub struct Cell<'a, T>
+where
+    T: CoordFloat,
+{
+    voronoi: &'a Voronoi<T>,
+    index: usize,
+}
+
+impl<T> Debug for Cell<'_, T>
+where
+    T: CoordFloat,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Cell")
+            .field("site_index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T> Cell<'a, T>
+where
+    T: CoordFloat + FloatConst,
+{
+    /// The index of the site.
+    #[must_use]
+    pub const fn site_index(&self) -> usize {
+        self.index
+    }
+
+    /// The site ( in degrees ), the `Sitecoordinates` property of
+    /// [`Voronoi::polygons`].
+    #[must_use]
+    pub fn site(&self) -> Coord<T> {
+        self.voronoi.points[self.index]
+    }
+
+    /// The feature of the site, see [`Voronoi::feature`].
+    #[must_use]
+    pub fn feature(&self) -> Option<&'a Features<T>> {
+        self.voronoi.feature(self.index)
+    }
+
+    /// The cell, as in [`Voronoi::polygons`].
+    #[must_use]
+    pub fn polygon(&self) -> Polygon<T> {
+        let ring = self.ring();
+        let exterior: LineString<T> = ring
+            .iter()
+            .chain(ring.first())
+            .map(|&i| self.voronoi.delaunay.centers[i])
+            .collect();
+        Polygon::new(exterior, vec![])
+    }
+
+    /// The vertices of the cell ( in degrees ), clockwise seen from
+    /// outside the sphere, without repeating the first.
+    #[must_use]
+    pub fn vertices(&self) -> impl ExactSizeIterator<Item = Coord<T>> + 'a {
+        let centers = &self.voronoi.delaunay.centers;
+        self.ring().iter().map(|&i| centers[i])
+    }
+
+    /// The sites sharing an edge of the triangulation with this one.
+    #[must_use]
+    pub fn neighbors(&self) -> &'a [usize] {
+        self.voronoi
+            .delaunay
+            .neighbors
+            .get(&self.index)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// The area of the cell, on the sphere given by [`Voronoi::radius`].
+    ///
+    /// Zero for a degenerate cell.
+    #[must_use]
+    pub fn area(&self) -> T {
+        let r = self.voronoi.radius();
+        self.voronoi
+            .delaunay
+            .cell_area(self.index)
+            .map_or_else(T::zero, |area| area * r * r)
+    }
+
+    /// Whether the cell has fewer than three distinct vertices, or no
+    /// area, as the cell of a removed site.
+    #[must_use]
+    pub fn is_degenerate(&self) -> bool {
+        let ring = self.ring();
+        let distinct = (0..ring.len())
+            .filter(|&k| ring[k] != ring[(k + 1) % ring.len()])
+            .count();
+        distinct < 3 || self.area() == T::zero()
+    }
+
+    /// The cell vertices, indices into the circumcenters, without
+    /// repeating the first.
+    fn ring(&self) -> &'a [usize] {
+        self.voronoi.delaunay.cell(self.index)
+    }
+}
+
+impl<T> Voronoi<T>
+where
+    T: CoordFloat,
+{
+    /// Returns a view of the cell of each site, in site order, as the
+    /// features of [`Voronoi::polygons`].
+    ///
+    /// Nothing is computed until a view is read. Empty when the cells were
+    /// not computed, see [`crate::delaunay::Structures`].
+    pub fn cells(&self) -> impl ExactSizeIterator<Item = Cell<'_, T>> {
+        (0..self.delaunay.polygons.len()).map(|index| Cell {
+            voronoi: self,
+            index,
+        })
+    }
+
+    /// Returns a view of the cell of `site`.
+    ///
+    /// None when there is no such site, or when the cells were not
+    /// computed.
+    pub fn cell(&self, site: usize) -> Option<Cell<'_, T>> {
+        (site < self.delaunay.polygons.len()).then_some(Cell {
+            voronoi: self,
+            index: site,
+        })
+    }
+}
@@ -45,8 +45,6 @@ where
     }
 
     /// Returns all the cells.
-    /// # Panics
-    ///   If polygons must have a least one value, when called.
     pub fn cell_mesh(self) -> MultiLineString<T> {
         let delaunay = self.delaunay;
         let polygons = delaunay.polygons;
@@ -57,8 +55,10 @@ where
         let mut coordinates: Vec<LineString<T>> =
             Vec::with_capacity(polygons.len());
         for p in polygons {
-            //   TODO: remove panic and return a sensible default.
-            let mut p0 = *p.last().unwrap();
+            let Some(last) = p.last() else {
+                continue;
+            };
+            let mut p0 = *last;
             let mut p1 = p[0];
             for pi in p {
                 if p1 > p0 {
@@ -0,0 +1,60 @@
+use geo::Coord;
+use geo::CoordFloat;
+use num_traits::FloatConst;
+
+use super::ClearancePath;
+use super::Voronoi;
+
+impl<T> Voronoi<T>
+where
+    T: CoordFloat + FloatConst,
+{
+    /// Returns the path along the edges of the cells, the graph drawn by
+    /// [`Voronoi::cell_mesh`], which stays as far as possible from the
+    /// sites.
+    ///
+    /// `from` and `to`, in degrees, are snapped onto the nearest cell
+    /// edges. Lengths are measured on the sphere given by
+    /// [`Voronoi::radius`]. See
+    /// [`crate::delaunay::Delaunay::max_clearance_path`].
+    #[must_use]
+    pub fn max_clearance_path(
+        &self,
+        from: &Coord<T>,
+        to: &Coord<T>,
+    ) -> Option<ClearancePath<T>> {
+        let path = self.delaunay.max_clearance_path(from, to)?;
+        Some(self.scaled(path))
+    }
+
+    /// Returns the shortest path along the edges of the cells which stays
+    /// at least `clearance` away from the sites.
+    ///
+    /// `from` and `to`, in degrees, are snapped onto the nearest cell
+    /// edges. Lengths are measured on the sphere given by
+    /// [`Voronoi::radius`]. See
+    /// [`crate::delaunay::Delaunay::clearance_path`].
+    #[must_use]
+    pub fn clearance_path(
+        &self,
+        from: &Coord<T>,
+        to: &Coord<T>,
+        clearance: T,
+    ) -> Option<ClearancePath<T>> {
+        let path = self.delaunay.clearance_path(
+            from,
+            to,
+            clearance / self.radius(),
+        )?;
+        Some(self.scaled(path))
+    }
+
+    /// From the unit sphere to the sphere given by [`Voronoi::radius`].
+    fn scaled(&self, path: ClearancePath<T>) -> ClearancePath<T> {
+        ClearancePath {
+            length: path.length * self.radius(),
+            clearance: path.clearance * self.radius(),
+            ..path
+        }
+    }
+}
@@ -9,21 +9,94 @@ impl<T> Voronoi<T>
 where
     T: CoordFloat,
 {
-    /// Returns the index associated with the given point.
-    pub fn find(&mut self, p: &Coord<T>, radius: Option<T>) -> Option<usize> {
-        self.found = self.delaunay.find(p, self.found);
-        match radius {
-            Some(radius) => match self.found {
-                Some(found) => {
-                    if distance(p, &self.points[found]) < radius {
-                        Some(found)
-                    } else {
-                        None
-                    }
-                }
-                None => None,
-            },
-            None => self.found,
-        }
+    /// Returns the index of the site nearest to `p`, in degrees.
+    ///
+    /// The optional cutoff `radius` is measured on the sphere given by
+    /// [`Voronoi::radius`].
+    #[must_use]
+    pub fn find(&self, p: &Coord<T>, radius: Option<T>) -> Option<usize> {
+        self.find_from(p, None, radius).0
+    }
+
+    /// As [`Voronoi::find`], walking from the site `hint`.
+    ///
+    /// Returns the site found, if within the cutoff `radius`, and the
+    /// nearest site, the hint for a following query close to `p`.
+    #[must_use]
+    pub fn find_from(
+        &self,
+        p: &Coord<T>,
+        hint: Option<usize>,
+        radius: Option<T>,
+    ) -> (Option<usize>, Option<usize>) {
+        let nearest = self.delaunay.find(p, hint);
+        (
+            nearest.filter(|&site| self.within(p, site, radius)),
+            nearest,
+        )
+    }
+
+    /// Returns the index of the site nearest to each point, as
+    /// [`Voronoi::find`].
+    ///
+    /// Faster than repeated calls to [`Voronoi::find`], see
+    /// [`crate::delaunay::Delaunay::find_many`].
+    #[must_use]
+    pub fn find_many(
+        &self,
+        points: &[Coord<T>],
+        radius: Option<T>,
+    ) -> Vec<Option<usize>> {
+        self.delaunay
+            .find_many(points)
+            .into_iter()
+            .zip(points)
+            .map(|(nearest, p)| {
+                nearest.filter(|&site| self.within(p, site, radius))
+            })
+            .collect()
+    }
+
+    /// Returns the `k` sites nearest to `p`, in degrees, with their
+    /// distance to `p`, nearest first.
+    ///
+    /// Distances are measured on the sphere given by [`Voronoi::radius`].
+    #[must_use]
+    pub fn k_nearest(&self, p: &Coord<T>, k: usize) -> Vec<(usize, T)> {
+        let radius = self.radius();
+        self.delaunay
+            .k_nearest(p, k)
+            .into_iter()
+            .map(|(site, d)| (site, d * radius))
+            .collect()
+    }
+
+    /// Returns the sites within `radius` of `p`, in degrees, with their
+    /// distance to `p`, nearest first.
+    ///
+    /// Distances are measured on the sphere given by [`Voronoi::radius`].
+    #[must_use]
+    pub fn sites_within(&self, p: &Coord<T>, radius: T) -> Vec<(usize, T)> {
+        let r = self.radius();
+        self.delaunay
+            .sites_within(p, radius / r)
+            .into_iter()
+            .map(|(site, d)| (site, d * r))
+            .collect()
+    }
+
+    /// Returns the sites whose cell intersects the cap of `radius` around
+    /// `p`, in degrees, in increasing order.
+    ///
+    /// The radius is measured on the sphere given by [`Voronoi::radius`].
+    #[must_use]
+    pub fn cells_within(&self, p: &Coord<T>, radius: T) -> Vec<usize> {
+        self.delaunay.cells_within(p, radius / self.radius())
+    }
+
+    fn within(&self, p: &Coord<T>, site: usize, radius: Option<T>) -> bool {
+        radius.is_none_or(|radius| {
+            distance(p, &self.points[site]) * self.radius() < radius
+        })
     }
 }
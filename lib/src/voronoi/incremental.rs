@@ -0,0 +1,78 @@
+use std::rc::Rc;
+
+use geo::CoordFloat;
+use geo_types::Coord;
+use num_traits::FloatConst;
+use num_traits::FromPrimitive;
+
+use super::Rejection;
+use super::Units;
+use super::Update;
+use super::UpdateError;
+use super::Voronoi;
+
+impl<T> Voronoi<T>
+where
+    T: 'static + CoordFloat + Default + FloatConst + FromPrimitive,
+{
+    /// Adds a site without recomputing the whole diagram.
+    ///
+    /// The coordinates, in the units of the diagram, become a new input
+    /// element. Within the tolerance of an existing site they are merged
+    /// into it. See [`crate::delaunay::Delaunay::insert_site`].
+    ///
+    /// # Errors
+    ///  When the coordinates are not finite, or when the triangulation had
+    ///  to be rebuilt and failed. The diagram is then unchanged.
+    pub fn insert_site(
+        &mut self,
+        site: &Coord<T>,
+    ) -> Result<Update, UpdateError> {
+        let point = if self.config.units() == Units::Radians {
+            Coord {
+                x: site.x.to_degrees(),
+                y: site.y.to_degrees(),
+            }
+        } else {
+            *site
+        };
+        // Releases the shared points, which are then updated in place.
+        self.points = Rc::default();
+        let update = self
+            .delaunay
+            .insert_site(&point, self.config.angular_tolerance());
+        self.points = self.delaunay.points.clone();
+        let update = update?;
+
+        let input = self.index.len();
+        self.index.push(Some(update.site));
+        if update.site == self.inputs.len() {
+            self.inputs.push(Vec::new());
+            self.valid.push(*site);
+        }
+        self.inputs[update.site].push(input);
+        Ok(update)
+    }
+
+    /// Removes a site without recomputing the whole diagram.
+    ///
+    /// The site keeps its index with an empty cell, and the input elements
+    /// merged into it are reported by [`Voronoi::rejected`] as removed.
+    ///
+    /// # Errors
+    ///  When there is no such site, or when the triangulation had to be
+    ///  rebuilt and failed. The diagram is then unchanged.
+    pub fn remove_site(&mut self, site: usize) -> Result<Update, UpdateError> {
+        self.points = Rc::default();
+        let update = self.delaunay.remove_site(site);
+        self.points = self.delaunay.points.clone();
+        let update = update?;
+
+        for input in core::mem::take(&mut self.inputs[site]) {
+            self.index[input] = None;
+            self.rejected.push((input, Rejection::Removed));
+        }
+        self.rejected.sort_unstable_by_key(|(input, _)| *input);
+        Ok(update)
+    }
+}
@@ -55,6 +55,8 @@ where
     }
 
     /// Returns an annotated Feature collection labelled with distance urquhart etc.
+    ///
+    /// The sites of each link are given by [`Voronoi::link_sites`].
     pub fn links(&self) -> FeatureCollection<T> {
         // if let Some(data) = data {
         //     match Self::try_from(data) {
@@ -68,7 +70,7 @@ where
             .delaunay
             .edges
             .iter()
-            .map(|e| distance(&points[e.0], &points[e.1]))
+            .map(|e| distance(&points[e.0], &points[e.1]) * self.radius())
             .collect();
         let urquhart = (self.delaunay.urquhart)(&distances);
         let features: Vec<Features<T>> = self
@@ -77,7 +79,7 @@ where
             .iter()
             .enumerate()
             .map(|(i, e)| {
-                let ls: LineString<T> = vec![points[0], points[e.1]].into();
+                let ls: LineString<T> = vec![points[e.0], points[e.1]].into();
                 Features {
                     properties: vec![
                         FeatureProperty::Source(self.valid[e.0]),
@@ -92,3 +94,36 @@ where
         FeatureCollection(features)
     }
 }
+
+impl<T> Voronoi<T>
+where
+    T: CoordFloat,
+{
+    /// The link from site `a` to site `b`, labelled as in
+    /// [`Voronoi::links`] but without the urquhart flag.
+    pub(super) fn link(&self, a: usize, b: usize, length: T) -> Features<T> {
+        let ls: LineString<T> = vec![self.points[a], self.points[b]].into();
+        Features {
+            properties: vec![
+                FeatureProperty::Source(self.valid[a]),
+                FeatureProperty::Target(self.valid[b]),
+                FeatureProperty::Length(length),
+            ],
+            geometry: vec![Geometry::LineString(ls)],
+        }
+    }
+
+    /// The source and target site of each link, in the order of
+    /// [`Voronoi::links`].
+    ///
+    /// The features of the sites are given by [`Voronoi::feature`].
+    pub fn link_sites(&self) -> Vec<(usize, usize)> {
+        self.delaunay.edges.iter().copied().collect()
+    }
+
+    /// The length of the arc between two sites, on the sphere given by
+    /// [`Voronoi::radius`].
+    pub(super) fn length(&self, a: usize, b: usize) -> T {
+        distance(&self.points[a], &self.points[b]) * self.radius()
+    }
+}
@@ -3,43 +3,62 @@ use core::fmt::Display;
 use std::rc::Rc;
 
 use float_next_after::NextAfter;
-use geo::centroid::Centroid;
 use geo::CoordFloat;
+use geo::GeoFloat;
 use geo::GeoNum;
 use geo::Geometry;
+use geo::LineString;
+use geo::MultiLineString;
+use geo::MultiPolygon;
 use geo::Point;
+use geo::Polygon;
 use geo_types::Coord;
 use num_traits::Bounded;
 use num_traits::FloatConst;
 use num_traits::FromPrimitive;
 use num_traits::Signed;
 
+use d3_geo_rs::cartesian::normalize;
+use d3_geo_rs::cartesian::spherical;
+use d3_geo_rs::data_object::FeatureCollection;
+use d3_geo_rs::data_object::Features;
+
 use super::delaunay::Delaunay;
+use super::delaunay::Structures;
 
+mod builder;
+mod cell;
 mod cell_mesh;
+mod clearance;
 mod find;
 mod hull;
+mod incremental;
 mod links;
 mod mesh;
+mod path;
+mod planar;
 mod polygons;
+mod relax;
+mod skeleton;
+mod spanning_tree;
+mod stats;
 mod triangles;
 
-/// Return type used by `.x()` and `.y()`
-#[allow(missing_debug_implementations)]
-pub enum XYReturn<T>
-where
-    T: CoordFloat,
-{
-    /// Voronoi
-    Voronoi(Voronoi<T>),
-    /// Function
-    Func(VTransform<T>),
-}
+pub use crate::delaunay::CellStats;
+pub use crate::delaunay::ClearancePath;
+pub use crate::delaunay::Update;
+pub use crate::error::ConstructionError;
+pub use crate::error::Rejection;
+pub use crate::error::UpdateError;
 
-type XYReturnDefault<T> = XYReturn<T>;
+pub use builder::Units;
+pub use builder::VoronoiBuilder;
+pub use cell::Cell;
+pub use path::Path;
+pub use relax::Relaxation;
 
-/// Velocity Transform.
-pub type VTransform<T> = Box<dyn Fn(&dyn Centroid<Output = Point<T>>) -> T>;
+/// Site accessor: maps an input geometry onto one coordinate of its site.
+pub type VTransform<T> = Rc<dyn Fn(&Geometry<T>) -> T>;
 
 /// Holds data centered on a [`Delaunay`] instance.
 pub struct Voronoi<T>
@@ -50,13 +69,17 @@ where
     #[allow(clippy::type_complexity)]
     pub delaunay: Delaunay<T>,
     data: Option<Geometry<T>>,
-    found: Option<usize>,
     //Points: Rc needed here as the edges, triangles, neighbors etc all index into that vec.
     points: Rc<Vec<Coord<T>>>,
     valid: Vec<Coord<T>>,
-    // Option<Box<impl Fn(&dyn Centroid<Output = Coord<T>>) -> T>>
-    vx: VTransform<T>,
-    vy: VTransform<T>,
+    // When constructed from features: the feature of each valid site.
+    features: Vec<Features<T>>,
+    // Maps each input element onto its site.
+    index: Vec<Option<usize>>,
+    // Maps each site onto the input elements merged into it.
+    inputs: Vec<Vec<usize>>,
+    rejected: Vec<(usize, Rejection)>,
+    config: VoronoiBuilder<T>,
 }
 
 impl<T> Debug for Voronoi<T>
@@ -67,51 +90,124 @@ where
         f.debug_tuple("Centroid<T>")
             .field(&self.delaunay)
             .field(&self.data)
-            .field(&self.found)
             .field(&self.points)
             .field(&self.valid)
+            .field(&self.features)
+            .field(&self.index)
+            .field(&self.inputs)
+            .field(&self.rejected)
+            .field(&self.config)
             .finish()
     }
 }
 
 impl<T> Default for Voronoi<T>
 where
-    T: CoordFloat + FloatConst + FromPrimitive,
+    T: 'static + CoordFloat + FloatConst + FromPrimitive + GeoFloat,
 {
     fn default() -> Self {
         Self {
             data: None,
             delaunay: Delaunay::default(),
-            found: None,
             points: Rc::new(Vec::new()),
             valid: Vec::new(),
-            vx: Box::new(|d: &dyn Centroid<Output = Point<T>>| {
-                d.centroid().x()
-            }),
-            vy: Box::new(|d: &dyn Centroid<Output = Point<T>>| {
-                d.centroid().y()
-            }),
+            features: Vec::new(),
+            index: Vec::new(),
+            inputs: Vec::new(),
+            rejected: Vec::new(),
+            config: VoronoiBuilder::default(),
         }
     }
 }
 
-/// Geo-Voronoi construction error.
-///
-/// Unsupport Geometry object used as input.
-/// Please file and issue.
-#[derive(Debug, Clone)]
-pub struct ConstructionError;
+impl<T> TryFrom<Geometry<T>> for Voronoi<T>
+where
+    T: 'static
+        + Bounded
+        + CoordFloat
+        + Default
+        + FloatConst
+        + FromPrimitive
+        + GeoNum
+        + NextAfter
+        + Signed,
+{
+    type Error = ConstructionError;
 
-impl core::fmt::Display for ConstructionError {
-    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        write!(
-            f,
-            "Must implement Voronoi::new for other DataObject<T> types"
-        )
+    /// # Errors
+    ///  See [`Voronoi::with_data`].
+    fn try_from(data: Geometry<T>) -> Result<Self, ConstructionError> {
+        Self::default().with_data(data)
     }
 }
 
-impl<T> TryFrom<Geometry<T>> for Voronoi<T>
+impl<T> TryFrom<FeatureCollection<T>> for Voronoi<T>
+where
+    T: 'static
+        + Bounded
+        + CoordFloat
+        + Default
+        + FloatConst
+        + FromPrimitive
+        + GeoNum
+        + NextAfter
+        + Signed,
+{
+    type Error = ConstructionError;
+
+    /// # Errors
+    ///  See [`Voronoi::with_features`].
+    fn try_from(
+        FeatureCollection(features): FeatureCollection<T>,
+    ) -> Result<Self, ConstructionError> {
+        Self::try_from(features)
+    }
+}
+
+impl<T> TryFrom<Vec<Features<T>>> for Voronoi<T>
+where
+    T: 'static
+        + Bounded
+        + CoordFloat
+        + Default
+        + FloatConst
+        + FromPrimitive
+        + GeoNum
+        + NextAfter
+        + Signed,
+{
+    type Error = ConstructionError;
+
+    /// # Errors
+    ///  See [`Voronoi::with_features`].
+    fn try_from(features: Vec<Features<T>>) -> Result<Self, ConstructionError> {
+        Self::default().with_features(features)
+    }
+}
+
+impl<T> TryFrom<Vec<Polygon<T>>> for Voronoi<T>
+where
+    T: 'static
+        + Bounded
+        + CoordFloat
+        + Default
+        + FloatConst
+        + FromPrimitive
+        + GeoNum
+        + NextAfter
+        + Signed,
+{
+    type Error = ConstructionError;
+
+    /// # Errors
+    ///  See [`Voronoi::with_data`].
+    fn try_from(polygons: Vec<Polygon<T>>) -> Result<Self, ConstructionError> {
+        Self::default()
+            .with_data(Geometry::MultiPolygon(MultiPolygon(polygons)))
+    }
+}
+
+impl<T> TryFrom<Vec<LineString<T>>> for Voronoi<T>
 where
     T: 'static
         + Bounded
@@ -124,78 +220,268 @@ where
         + Signed,
 {
     type Error = ConstructionError;
-    /// If the input is a collection we act only on the first element in the collection.
-    /// by copying over the data into a new single element before proceeding.
+
+    /// # Errors
+    ///  See [`Voronoi::with_data`].
+    fn try_from(
+        line_strings: Vec<LineString<T>>,
+    ) -> Result<Self, ConstructionError> {
+        Self::default()
+            .with_data(Geometry::MultiLineString(MultiLineString(line_strings)))
+    }
+}
+
+impl<T> Voronoi<T>
+where
+    T: 'static
+        + Bounded
+        + CoordFloat
+        + Default
+        + FloatConst
+        + FromPrimitive
+        + GeoNum
+        + NextAfter
+        + Signed,
+{
+    /// Computes the diagram, one site per element of the input, with the
+    /// configuration of this diagram.
+    ///
+    /// The site of each element is given by the `x()` and `y()`
+    /// accessors, by default the centroid of the element.
     ///
     /// # Errors
-    ///  A `Geometry::Multipoint` object must be input.
-    fn try_from(data: Geometry<T>) -> Result<Self, ConstructionError> {
-        // let delaunay_return: Option<GeoDelaunay> = None;
-
-        // On finding a Features Collection take the first element only, drop other elements.
-        // match data {
-        //     DataObject<T>::FeatureCollection { mut features } => {
-        //         // TODO: .remove() panics it it can't complete - consider trapping.
-        //         let mut first_feature = features.remove(0);
-        //         let geometry = first_feature.geometry.remove(0);
-        //         let feature = FeatureStruct {
-        //             properties: Vec::new(),
-        //             geometry,
-        //         };
-        //         data = DataObject<T>::Feature { feature };
-        //     }
-        //     _ => {
-        //         // Other DataTypes variants.
-        //     }
-        // };
-
-        let mut v = Self {
-            data: Some(data),
-            ..Self::default()
+    ///  The input must be a `MultiPoint`, `MultiLineString`,
+    ///  `MultiPolygon` or `GeometryCollection` with enough valid sites.
+    pub fn with_data(
+        self,
+        data: Geometry<T>,
+    ) -> Result<Self, ConstructionError> {
+        // Transform points using vx() and vy().
+        let sites: Vec<Option<(Coord<T>, Coord<T>)>> = match &data {
+            Geometry::MultiPoint(mp) => mp
+                .iter()
+                .map(|p| Some(self.site(&Geometry::Point(*p))))
+                .collect(),
+            Geometry::MultiLineString(mls) => mls
+                .iter()
+                .map(|ls| Some(self.site(&Geometry::LineString(ls.clone()))))
+                .collect(),
+            Geometry::MultiPolygon(mp) => mp
+                .iter()
+                .map(|p| Some(self.site(&Geometry::Polygon(p.clone()))))
+                .collect(),
+            Geometry::GeometryCollection(gc) => {
+                gc.iter().map(|g| Some(self.site(g))).collect()
+            }
+            g => return Err(ConstructionError::unsupported(g)),
         };
 
-        // Data sanitization:-
-        // Transform points using vx() and vy().
-        // Remove infinities, store list of untransformed - valid points.
-
-        match v.data {
-            // Some(FeatureCollection { features: f }) => {
-            //     f.iter()
-            //         .map(|d| ((v.vx)(d), (v.vy)(d), d.clone()))
-            //         .filter(|t| (t.0 + t.1).is_finite());
-            // }
-            Some(Geometry::MultiPoint(ref data)) => {
-                let temp: Vec<(T, T, Point<T>)> = data
-                    .iter()
-                    .map(|d| {
-                        ((Self::default().vx)(d), (Self::default().vy)(d), *d)
-                    })
-                    .filter(|(d0, d1, _)| (*d0 + *d1).is_finite())
-                    .collect();
-                let points: Vec<Coord<T>> = temp
-                    .iter()
-                    .map(|(d0, d1, _)| Coord { x: *d0, y: *d1 })
-                    .collect();
-                v.points = points.clone().into();
-                v.valid = temp
-                    .iter()
-                    .map(|d| Coord {
-                        x: d.2.x(),
-                        y: d.2.y(),
-                    })
-                    .collect();
-                v.delaunay = match Delaunay::<T>::try_from(&points) {
-                    Ok(delaunay) => delaunay,
-                    Err(_) => return Err(ConstructionError),
+        self.sites(Some(data), &sites, Vec::new(), None)
+    }
+
+    /// Computes the diagram, one site per feature, with the configuration
+    /// of this diagram.
+    ///
+    /// The site of each feature is given by applying the `x()` and `y()`
+    /// accessors to its first geometry.
+    ///
+    /// The feature travels with its site, see [`Voronoi::feature`]. Its
+    /// properties are appended to those of the site's cell in
+    /// `polygons()`, and [`Voronoi::triangle_sites`] and
+    /// [`Voronoi::link_sites`] give the sites of `triangles()` and
+    /// `links()`.
+    ///
+    /// # Errors
+    ///  If too few features have a valid site.
+    pub fn with_features(
+        self,
+        features: Vec<Features<T>>,
+    ) -> Result<Self, ConstructionError> {
+        let sites = features
+            .iter()
+            .map(|f| f.geometry.first().map(|g| self.site(g)))
+            .collect::<Vec<_>>();
+        self.sites(None, &sites, features, None)
+    }
+
+    /// Computes the diagram, one site per coordinate, with the
+    /// configuration of this diagram.
+    ///
+    /// The coordinates are the sites, the accessors are not applied.
+    ///
+    /// # Errors
+    ///  If too few coordinates are valid sites.
+    pub fn with_coords(
+        self,
+        coords: &[Coord<T>],
+    ) -> Result<Self, ConstructionError> {
+        let radians = self.config.units() == Units::Radians;
+        let sites: Vec<_> = coords
+            .iter()
+            .map(|c| {
+                let site = if radians {
+                    Coord {
+                        x: c.x.to_degrees(),
+                        y: c.y.to_degrees(),
+                    }
+                } else {
+                    *c
                 };
+                Some((site, *c))
+            })
+            .collect();
+        self.sites(None, &sites, Vec::new(), None)
+    }
+
+    /// Computes the diagram, one site per 3D cartesian vector, with the
+    /// configuration of this diagram.
+    ///
+    /// The vectors are normalized, then used as is in the geometric
+    /// computations. Sites are reported in degrees.
+    ///
+    /// # Errors
+    ///  If too few vectors are valid sites.
+    pub fn with_unit_vectors(
+        self,
+        vectors: &[[T; 3]],
+    ) -> Result<Self, ConstructionError> {
+        let vectors: Vec<[T; 3]> = vectors.iter().map(normalize).collect();
+        let sites: Vec<_> = vectors
+            .iter()
+            .map(|v| {
+                let site = spherical(v);
+                Some((site, site))
+            })
+            .collect();
+        self.sites(None, &sites, Vec::new(), Some(&vectors))
+    }
+
+    /// Computes the diagram from a slice of coordinates, in degrees.
+    ///
+    /// # Errors
+    ///  See [`Voronoi::with_coords`].
+    pub fn from_coords(coords: &[Coord<T>]) -> Result<Self, ConstructionError> {
+        Self::default().with_coords(coords)
+    }
+
+    /// Computes the diagram from points, in degrees.
+    ///
+    /// # Errors
+    ///  See [`Voronoi::with_coords`].
+    pub fn from_points(
+        points: impl IntoIterator<Item = Point<T>>,
+    ) -> Result<Self, ConstructionError> {
+        let coords: Vec<Coord<T>> = points.into_iter().map(|p| p.0).collect();
+        Self::from_coords(&coords)
+    }
+
+    /// Computes the diagram from 3D cartesian vectors.
+    ///
+    /// # Errors
+    ///  See [`Voronoi::with_unit_vectors`].
+    pub fn from_unit_vectors(
+        vectors: &[[T; 3]],
+    ) -> Result<Self, ConstructionError> {
+        Self::default().with_unit_vectors(vectors)
+    }
+
+    /// Applies the accessors to an element of the input.
+    ///
+    /// Returns the site, paired with the untransformed coordinates
+    /// reported as the `Site` property: the point itself for point input,
+    /// otherwise the site.
+    fn site(&self, d: &Geometry<T>) -> (Coord<T>, Coord<T>) {
+        let mut site = Coord {
+            x: (self.config.vx)(d),
+            y: (self.config.vy)(d),
+        };
+        if self.config.units() == Units::Radians {
+            site.x = site.x.to_degrees();
+            site.y = site.y.to_degrees();
+        }
+        match d {
+            Geometry::Point(p) => (site, p.0),
+            _ => (site, site),
+        }
+    }
+
+    /// Data sanitization:-
+    /// Remove infinities and merge coincident sites, keeping each
+    /// surviving site aligned with its untransformed coordinates and
+    /// ( if any ) its feature.
+    ///
+    /// `sites` pairs the transformed point with the untransformed input,
+    /// None marks a feature without geometry.
+    ///
+    /// Nothing is kept from an earlier input, only the configuration.
+    fn sites(
+        mut self,
+        data: Option<Geometry<T>>,
+        sites: &[Option<(Coord<T>, Coord<T>)>],
+        features: Vec<Features<T>>,
+        vectors: Option<&[[T; 3]]>,
+    ) -> Result<Self, ConstructionError> {
+        self.data = data;
+        self.valid.clear();
+        self.features.clear();
+        self.inputs.clear();
+        self.rejected.clear();
+        let n_sites = sites.len();
+        let mut features: Vec<Option<Features<T>>> =
+            features.into_iter().map(Some).collect();
+        let mut points = Vec::with_capacity(sites.len());
+        let mut finite = Vec::with_capacity(sites.len());
+        for (i, site) in sites.iter().enumerate() {
+            match site {
+                None => self.rejected.push((i, Rejection::MissingGeometry)),
+                Some((point, _)) if !(point.x + point.y).is_finite() => {
+                    self.rejected.push((i, Rejection::NonFinite));
+                }
+                Some((point, _)) => {
+                    points.push(*point);
+                    finite.push(i);
+                }
+            }
+        }
+        if points.is_empty() && n_sites > 0 {
+            return Err(ConstructionError::AllSitesNonFinite);
+        }
+        let tolerance = self.config.angular_tolerance();
+        let structures = self.config.structures();
+        self.delaunay = match vectors {
+            Some(vectors) => {
+                let vectors: Vec<[T; 3]> =
+                    finite.iter().map(|&i| vectors[i]).collect();
+                Delaunay::try_with_vectors(
+                    &points, &vectors, tolerance, structures,
+                )?
             }
             None => {
-                v = Self::default();
+                Delaunay::try_with_tolerance(&points, tolerance, &structures)?
             }
-            _ => return Err(ConstructionError),
-        }
+        };
 
-        Ok(v)
+        // Each site is represented by the first input merged into it.
+        self.index = vec![None; n_sites];
+        for (input, site) in finite.into_iter().zip(&self.delaunay.index) {
+            self.index[input] = Some(*site);
+            if *site == self.inputs.len() {
+                self.inputs.push(Vec::new());
+            }
+            self.inputs[*site].push(input);
+            if *site == self.valid.len() {
+                if let Some((_, valid)) = sites[input] {
+                    self.valid.push(valid);
+                }
+                if let Some(feature) =
+                    features.get_mut(input).and_then(Option::take)
+                {
+                    self.features.push(feature);
+                }
+            }
+        }
+        self.points = self.delaunay.points.clone();
+        Ok(self)
     }
 }
 
@@ -203,35 +489,95 @@ impl<T> Voronoi<T>
 where
     T: CoordFloat,
 {
-    /// Sets the `x()` override function.
-    pub fn x(
-        mut self,
-        f: Option<
-            Box<impl Fn(&dyn Centroid<Output = Point<T>>) -> T + 'static>,
-        >,
-    ) -> XYReturnDefault<T> {
-        match f {
-            None => XYReturn::Func(self.vx),
-            Some(f) => {
-                self.vx = f;
-                XYReturn::Voronoi(self)
-            }
-        }
+    /// The feature associated with a site.
+    ///
+    /// None unless the diagram was constructed from features.
+    pub fn feature(&self, site: usize) -> Option<&Features<T>> {
+        self.features.get(site)
     }
 
-    /// Sets the `y()` override function.
-    pub fn y(
-        mut self,
-        f: Option<
-            Box<impl Fn(&dyn Centroid<Output = Point<T>>) -> T + 'static>,
-        >,
-    ) -> XYReturnDefault<T> {
-        match f {
-            None => XYReturn::Func(self.vy),
-            Some(f) => {
-                self.vy = f;
-                XYReturn::Voronoi(self)
-            }
-        }
+    /// Maps each input element onto the index of its site.
+    ///
+    /// Elements merged by the tolerance share a site. None marks an
+    /// element without a valid site.
+    pub fn index(&self) -> &[Option<usize>] {
+        &self.index
+    }
+
+    /// The site of an input element, see [`Voronoi::index`].
+    pub fn site_index(&self, input: usize) -> Option<usize> {
+        self.index.get(input).copied().flatten()
+    }
+
+    /// The input elements merged into a site, in input order.
+    ///
+    /// The first element is the one whose coordinates and feature
+    /// represent the site.
+    pub fn inputs(&self, site: usize) -> &[usize] {
+        self.inputs.get(site).map_or(&[], Vec::as_slice)
+    }
+
+    /// The input elements which were not given a site, in input order,
+    /// with the reason for each.
+    pub fn rejected(&self) -> &[(usize, Rejection)] {
+        &self.rejected
+    }
+
+    /// Re-indexes a per site output by input position.
+    ///
+    /// Elements merged into one site all receive a copy of its value,
+    /// rejected elements receive None.
+    pub fn by_input<U>(&self, per_site: &[U]) -> Vec<Option<U>>
+    where
+        U: Clone,
+    {
+        self.index
+            .iter()
+            .map(|site| site.and_then(|site| per_site.get(site).cloned()))
+            .collect()
+    }
+
+    /// Returns a builder, used to configure the diagram.
+    #[must_use]
+    pub fn builder() -> VoronoiBuilder<T>
+    where
+        T: 'static + GeoFloat,
+    {
+        VoronoiBuilder::default()
+    }
+
+    /// Returns the configuration used to construct the diagram.
+    pub const fn config(&self) -> &VoronoiBuilder<T> {
+        &self.config
+    }
+
+    /// Returns the `x()` accessor.
+    pub fn x(&self) -> &VTransform<T> {
+        self.config.x()
+    }
+
+    /// Returns the `y()` accessor.
+    pub fn y(&self) -> &VTransform<T> {
+        self.config.y()
+    }
+
+    /// Returns the units of the accessors output.
+    pub const fn units(&self) -> Units {
+        self.config.units()
+    }
+
+    /// Returns the sphere radius.
+    pub const fn radius(&self) -> T {
+        self.config.radius()
+    }
+
+    /// Returns the distance below which sites are merged.
+    pub const fn tolerance(&self) -> T {
+        self.config.tolerance()
+    }
+
+    /// Returns the selection of derived structures computed.
+    pub const fn structures(&self) -> Structures {
+        self.config.structures()
     }
 }
@@ -0,0 +1,168 @@
+use std::collections::BinaryHeap;
+use std::collections::VecDeque;
+
+use geo::CoordFloat;
+use geo::LineString;
+
+use d3_geo_rs::distance::distance;
+
+use crate::queue::Queued;
+
+use super::Voronoi;
+
+/// A path between two sites along Delaunay edges.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Path<T>
+where
+    T: CoordFloat,
+{
+    /// The sites visited, from the first to the last.
+    pub sites: Vec<usize>,
+    /// The sum of the weights of the edges followed.
+    pub length: T,
+    /// The sites visited, as a line.
+    pub geometry: LineString<T>,
+}
+
+impl<T> Voronoi<T>
+where
+    T: CoordFloat,
+{
+    /// Returns the shortest path from the site `from` to the site `to`
+    /// along Delaunay edges, weighted by their length.
+    ///
+    /// Lengths are measured on the sphere given by [`Voronoi::radius`].
+    /// The search is an A* search, guided by the distance to `to`.
+    ///
+    /// None when either index is not a site, or when `to` cannot be
+    /// reached.
+    #[must_use]
+    pub fn shortest_path(&self, from: usize, to: usize) -> Option<Path<T>> {
+        let target = *self.points.get(to)?;
+        self.search(
+            from,
+            to,
+            |a, b| self.length(a, b),
+            |i| distance(&self.points[i], &target) * self.radius(),
+        )
+    }
+
+    /// Returns the shortest path from the site `from` to the site `to`
+    /// along Delaunay edges, weighted by `weight`, a function of the two
+    /// ends of an edge.
+    ///
+    /// Edges with a weight which is negative or not finite are not
+    /// followed. The search is Dijkstra's algorithm.
+    ///
+    /// None when either index is not a site, or when `to` cannot be
+    /// reached.
+    #[must_use]
+    pub fn shortest_path_by(
+        &self,
+        from: usize,
+        to: usize,
+        weight: impl Fn(usize, usize) -> T,
+    ) -> Option<Path<T>> {
+        self.search(from, to, weight, |_| T::zero())
+    }
+
+    /// Returns the number of Delaunay edges on the shortest path from the
+    /// site `source` to each site, None for the sites which cannot be
+    /// reached.
+    ///
+    /// Empty when `source` is not a site.
+    #[must_use]
+    pub fn hops(&self, source: usize) -> Vec<Option<usize>> {
+        if source >= self.points.len() {
+            return Vec::new();
+        }
+        let mut hops = vec![None; self.points.len()];
+        hops[source] = Some(0);
+        let mut queue = VecDeque::from([(source, 0)]);
+        while let Some((site, n)) = queue.pop_front() {
+            for &i in self.delaunay.neighbors.get(&site).into_iter().flatten() {
+                if hops[i].is_none() {
+                    hops[i] = Some(n + 1);
+                    queue.push_back((i, n + 1));
+                }
+            }
+        }
+        hops
+    }
+
+    /// Searches for the shortest path, the `heuristic` bounding the length
+    /// of the remaining path from below.
+    fn search(
+        &self,
+        from: usize,
+        to: usize,
+        weight: impl Fn(usize, usize) -> T,
+        heuristic: impl Fn(usize) -> T,
+    ) -> Option<Path<T>> {
+        let n = self.points.len();
+        if from >= n || to >= n {
+            return None;
+        }
+        let mut lengths = vec![T::infinity(); n];
+        let mut previous = vec![None; n];
+        lengths[from] = T::zero();
+        let mut queue = BinaryHeap::from([Queued {
+            priority: heuristic(from),
+            cost: T::zero(),
+            node: from,
+        }]);
+        while let Some(Queued {
+            cost: length,
+            node: site,
+            ..
+        }) = queue.pop()
+        {
+            if site == to {
+                return Some(self.path(&previous, to, length));
+            }
+            if length > lengths[site] {
+                // Stale, the site was reached by a shorter path.
+                continue;
+            }
+            for &i in self.delaunay.neighbors.get(&site).into_iter().flatten() {
+                let w = weight(site, i);
+                if !(w >= T::zero() && w.is_finite()) {
+                    continue;
+                }
+                let l = length + w;
+                if l < lengths[i] {
+                    lengths[i] = l;
+                    previous[i] = Some(site);
+                    queue.push(Queued {
+                        priority: l + heuristic(i),
+                        cost: l,
+                        node: i,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    /// Follows the path back from `to`.
+    fn path(
+        &self,
+        previous: &[Option<usize>],
+        to: usize,
+        length: T,
+    ) -> Path<T> {
+        let mut sites = vec![to];
+        let mut site = to;
+        while let Some(p) = previous[site] {
+            sites.push(p);
+            site = p;
+        }
+        sites.reverse();
+        let geometry = sites.iter().map(|&i| self.points[i]).collect();
+        Path {
+            sites,
+            length,
+            geometry,
+        }
+    }
+}
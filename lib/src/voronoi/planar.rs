@@ -0,0 +1,255 @@
+use core::fmt::Display;
+use core::ops::AddAssign;
+
+use approx::AbsDiffEq;
+use float_next_after::NextAfter;
+use geo::CoordFloat;
+use geo::GeoNum;
+use geo::Geometry;
+use geo::MultiPolygon;
+use geo::Polygon;
+use geo_types::Coord;
+use num_traits::AsPrimitive;
+use num_traits::Bounded;
+use num_traits::FloatConst;
+use num_traits::FromPrimitive;
+use num_traits::Signed;
+
+use d3_geo_rs::cartesian::normalize;
+use d3_geo_rs::cartesian::spherical;
+use d3_geo_rs::data_object::FeatureCollection;
+use d3_geo_rs::data_object::Features;
+
+use crate::delaunay::cartesian::cartesian;
+
+use super::Voronoi;
+
+/// Whether a unit vector is a pole, where the longitude is undefined.
+fn at_pole<T: CoordFloat>(v: &[T; 3]) -> bool {
+    v[0].hypot(v[1]) <= T::epsilon()
+}
+
+/// The latitude at which the great circle arc from `p` to `q` crosses the
+/// antimeridian.
+fn crossing<T: CoordFloat + FloatConst>(p: &[T; 3], q: &[T; 3]) -> T {
+    let d = p[1] - q[1];
+    if d == T::zero() {
+        return spherical(p).y;
+    }
+    let t = p[1] / d;
+    spherical(&normalize(&[
+        p[0] + t * (q[0] - p[0]),
+        p[1] + t * (q[1] - p[1]),
+        p[2] + t * (q[2] - p[2]),
+    ]))
+    .y
+}
+
+/// The part of `ring` in the half-plane `side * (x - at) >= 0`, as in the
+/// Sutherland–Hodgman algorithm.
+fn clip_x<T: CoordFloat>(ring: &[Coord<T>], at: T, side: T) -> Vec<Coord<T>> {
+    let mut out = Vec::with_capacity(ring.len() + 2);
+    for k in 0..ring.len() {
+        let (p, q) = (ring[k], ring[(k + 1) % ring.len()]);
+        let (dp, dq) = (side * (p.x - at), side * (q.x - at));
+        if dp >= T::zero() {
+            out.push(p);
+        }
+        if (dp >= T::zero()) != (dq >= T::zero()) {
+            let t = dp / (dp - dq);
+            out.push(Coord {
+                x: at,
+                y: p.y + t * (q.y - p.y),
+            });
+        }
+    }
+    out
+}
+
+/// Unwraps the longitudes of a ring of unit vectors, its inside on its
+/// right, into a planar ring which may extend one turn beyond ±180°.
+///
+/// The crossings of the antimeridian are vertices, and a pole inside the
+/// ring is bypassed along the edge of the map.
+fn unwrap<T: CoordFloat + FloatConst>(ring: &[[T; 3]]) -> Vec<Coord<T>> {
+    let half = T::from(180_f64).unwrap();
+    let full = half + half;
+    let quarter = half / (T::one() + T::one());
+    let wrap = |d: T| d - full * ((d + half) / full).floor();
+
+    let Some(start) = ring.iter().position(|v| !at_pole(v)) else {
+        return Vec::new();
+    };
+    let n = ring.len();
+    let first = spherical(&ring[start]);
+    let mut path = vec![first];
+    let (mut prev, mut raw, mut lon) = (&ring[start], first.x, first.x);
+    let mut pole = None;
+    for step in 1..=n {
+        let v = &ring[(start + step) % n];
+        if at_pole(v) {
+            pole = Some(quarter.copysign(v[2]));
+            continue;
+        }
+        let c = spherical(v);
+        let next = lon + wrap(c.x - raw);
+        if let Some(lat) = pole.take() {
+            // Along the meridians, and the edge of the map between them.
+            path.push(Coord { x: lon, y: lat });
+            path.push(Coord { x: next, y: lat });
+        } else {
+            let at = ((lon.min(next) + half) / full).floor() * full + half;
+            if at > lon.min(next) && at < lon.max(next) {
+                path.push(Coord {
+                    x: at,
+                    y: crossing(prev, v),
+                });
+            }
+        }
+        if step < n {
+            path.push(Coord { x: next, y: c.y });
+        }
+        (prev, raw, lon) = (v, c.x, next);
+    }
+    // Around a pole, the ring ends one turn away from its start: eastward
+    // the pole on its right is the south pole.
+    let turn = lon - first.x;
+    if turn.abs() <= half {
+        return path;
+    }
+    let turn = full.copysign(turn);
+    let lat = if turn > T::zero() { -quarter } else { quarter };
+    // From the antimeridian, to close along the edge of the map.
+    let j = path
+        .iter()
+        .position(|c| ((c.x + half) / full).fract() == T::zero())
+        .unwrap_or(0);
+    let shifted = |c: &Coord<T>| Coord {
+        x: c.x + turn,
+        y: c.y,
+    };
+    let mut path: Vec<Coord<T>> = path[j..]
+        .iter()
+        .copied()
+        .chain(path[..=j].iter().map(shifted))
+        .collect();
+    let (start, end) = (path[0].x, path[path.len() - 1].x);
+    path.push(Coord { x: end, y: lat });
+    path.push(Coord { x: start, y: lat });
+    path
+}
+
+/// Splits a ring of unit vectors, its inside on its right, into polygons
+/// within ±180° of longitude.
+fn split<T: CoordFloat + FloatConst>(ring: &[[T; 3]]) -> MultiPolygon<T> {
+    let half = T::from(180_f64).unwrap();
+    let full = half + half;
+    let path = unwrap(ring);
+    let polygons = [-full, T::zero(), full]
+        .into_iter()
+        .filter_map(|shift| {
+            let piece = clip_x(&path, shift - half, T::one());
+            let mut piece = clip_x(&piece, shift + half, -T::one());
+            for c in &mut piece {
+                c.x = c.x - shift;
+            }
+            piece.dedup();
+            while piece.len() > 1 && piece.first() == piece.last() {
+                piece.pop();
+            }
+            let area = (0..piece.len())
+                .map(|k| {
+                    let (p, q) = (piece[k], piece[(k + 1) % piece.len()]);
+                    p.x * q.y - q.x * p.y
+                })
+                .fold(T::zero(), |acc, x| acc + x);
+            (piece.len() >= 3 && area != T::zero())
+                .then(|| Polygon::new(piece.into(), vec![]))
+        })
+        .collect();
+    MultiPolygon(polygons)
+}
+
+/// Splits a polygon ( in degrees ) whose edges are great circle arcs, see
+/// [`split`].
+fn split_polygon<T: CoordFloat + FloatConst>(
+    polygon: &Polygon<T>,
+) -> MultiPolygon<T> {
+    let mut ring: Vec<[T; 3]> =
+        polygon.exterior().0.iter().map(cartesian).collect();
+    if ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    split(&ring)
+}
+
+/// Replaces the polygons of the features with their split form.
+fn split_features<T: CoordFloat + FloatConst>(
+    FeatureCollection(features): FeatureCollection<T>,
+) -> FeatureCollection<T> {
+    let features = features
+        .into_iter()
+        .map(|f| Features {
+            geometry: f
+                .geometry
+                .into_iter()
+                .map(|g| match g {
+                    Geometry::Polygon(p) => {
+                        Geometry::MultiPolygon(split_polygon(&p))
+                    }
+                    g => g,
+                })
+                .collect(),
+            properties: f.properties,
+        })
+        .collect();
+    FeatureCollection(features)
+}
+
+impl<T> Voronoi<T>
+where
+    T: AbsDiffEq<Epsilon = T>
+        + AddAssign
+        + AsPrimitive<T>
+        + Bounded
+        + CoordFloat
+        + Display
+        + Default
+        + FloatConst
+        + FromPrimitive
+        + GeoNum
+        + Signed
+        + NextAfter,
+{
+    /// Returns the polygons of [`Voronoi::polygons`] for planar consumers,
+    /// each cell a `MultiPolygon` within ±180° of longitude.
+    ///
+    /// A cell straddling the antimeridian is split there, and a cell
+    /// holding a pole is closed along the edge of the map, at ±90° of
+    /// latitude. Rings keep the orientation of the cells, and their edges
+    /// are straight in longitude and latitude.
+    pub fn polygons_planar(&self) -> FeatureCollection<T> {
+        split_features(self.polygons())
+    }
+
+    /// Returns the triangles of [`Voronoi::triangles`] for planar
+    /// consumers, see [`Voronoi::polygons_planar`].
+    pub fn triangles_planar(&self) -> FeatureCollection<T> {
+        split_features(self.triangles())
+    }
+
+    /// Returns the hull of [`Voronoi::hull`] for planar consumers, see
+    /// [`Voronoi::polygons_planar`].
+    pub fn hull_planar(&self) -> Option<MultiPolygon<T>> {
+        if self.delaunay.hull.is_empty() {
+            return None;
+        }
+        let ring: Vec<[T; 3]> = self
+            .delaunay
+            .hull
+            .iter()
+            .map(|&i| cartesian(&self.points[i]))
+            .collect();
+        Some(split(&ring))
+    }
+}
@@ -6,8 +6,7 @@ use float_next_after::NextAfter;
 use geo::CoordFloat;
 use geo::GeoNum;
 use geo::Geometry;
-use geo::LineString;
-use geo::Polygon;
+use geo::MultiPolygon;
 use num_traits::AsPrimitive;
 use num_traits::Bounded;
 use num_traits::FloatConst;
@@ -52,9 +51,9 @@ where
     }
     /// Returns polygons in the form of a feature collection.
     ///
-    /// None when either
-    /// the constructor fails, or
-    /// the delaunay instance is None.
+    /// The features are ordered by site. When the diagram was constructed
+    /// from features, the properties of the site's feature follow the
+    /// `Site`, `Sitecoordinates` and `Neighbors` properties.
     pub fn polygons(&self) -> FeatureCollection<T> {
         // if let Some(data) = data {
         //     match Self::try_from(data) {
@@ -67,31 +66,67 @@ where
             return FeatureCollection(Vec::new());
         }
 
-        let len = self.delaunay.polygons.len();
-        let mut features: Vec<Features<T>> = Vec::with_capacity(len);
-        for (i, poly) in self.delaunay.polygons.iter().enumerate() {
-            let mut poly_closed = Vec::with_capacity(poly.len() + 1);
-            poly_closed.extend_from_slice(poly);
-            poly_closed.push(poly[0]);
-            let exterior: LineString<T> = poly_closed
-                .iter()
-                .map(|&i| self.delaunay.centers[i])
-                .collect();
+        let features = self
+            .cells()
+            .map(|cell| Features {
+                geometry: vec![Geometry::Polygon(cell.polygon())],
+                properties: self.cell_properties(cell.site_index()),
+            })
+            .collect();
+        FeatureCollection(features)
+    }
 
-            let geometry = Geometry::Polygon(Polygon::new(exterior, vec![]));
-            let n =
-                self.delaunay.neighbors.get(&i).cloned().unwrap_or_default();
-            let properties: Vec<FeatureProperty<T>> = vec![
-                FeatureProperty::Site(self.valid[i]),
-                FeatureProperty::Sitecoordinates(self.points[i]),
-                FeatureProperty::Neighbors(n),
-            ];
-            let fs = Features {
-                geometry: vec![geometry],
-                properties,
-            };
-            features.push(fs);
+    /// Returns the polygons clipped to `mask`, in the form of a feature
+    /// collection.
+    ///
+    /// The features are those of [`Voronoi::polygons`], each geometry
+    /// being the part of the cell inside the mask, an empty
+    /// `MultiPolygon` for a cell outside it. See
+    /// [`crate::delaunay::Delaunay::polygons_clipped`] for the orientation
+    /// of the rings of the mask.
+    pub fn polygons_clipped(
+        &self,
+        mask: &MultiPolygon<T>,
+    ) -> FeatureCollection<T> {
+        if self.valid.is_empty() {
+            return FeatureCollection(Vec::new());
         }
+
+        let features = self
+            .delaunay
+            .polygons_clipped(mask)
+            .into_iter()
+            .enumerate()
+            .map(|(i, clipped)| Features {
+                geometry: vec![Geometry::MultiPolygon(clipped)],
+                properties: self.cell_properties(i),
+            })
+            .collect();
         FeatureCollection(features)
     }
+
+    /// The properties of the cell of site `i`: `Site`, `Sitecoordinates`
+    /// and `Neighbors`, then those of the site's feature.
+    fn cell_properties(&self, i: usize) -> Vec<FeatureProperty<T>> {
+        let n = self.delaunay.neighbors.get(&i).cloned().unwrap_or_default();
+        let mut properties: Vec<FeatureProperty<T>> = vec![
+            FeatureProperty::Site(self.valid[i]),
+            FeatureProperty::Sitecoordinates(self.points[i]),
+            FeatureProperty::Neighbors(n),
+        ];
+        if let Some(feature) = self.feature(i) {
+            properties.extend_from_slice(&feature.properties);
+        }
+        properties
+    }
+
+    /// Returns the polygons indexed by input position, so that entry `i`
+    /// is the cell of input element `i`.
+    ///
+    /// None marks an element which was not given a site, see
+    /// [`Voronoi::rejected`].
+    pub fn polygons_by_input(&self) -> Vec<Option<Features<T>>> {
+        let FeatureCollection(features) = self.polygons();
+        self.by_input(&features)
+    }
 }
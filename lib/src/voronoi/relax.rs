@@ -0,0 +1,157 @@
+use core::fmt::Debug;
+use core::fmt::Display;
+
+use float_next_after::NextAfter;
+use geo::CoordFloat;
+use geo::GeoNum;
+use geo_types::Coord;
+use num_traits::Bounded;
+use num_traits::FloatConst;
+use num_traits::FromPrimitive;
+use num_traits::Signed;
+
+use d3_geo_rs::cartesian::dot;
+
+use super::ConstructionError;
+use super::Structures;
+use super::Voronoi;
+
+/// The outcome of [`Voronoi::relax`].
+pub struct Relaxation<T>
+where
+    T: CoordFloat,
+{
+    /// The relaxed diagram.
+    pub voronoi: Voronoi<T>,
+    /// The energy of the diagram at the start of each iteration.
+    pub energies: Vec<T>,
+    /// The largest move of a site at each iteration.
+    pub moves: Vec<T>,
+    /// The last move was below the tolerance.
+    pub converged: bool,
+    /// The number of sites lost along the way, their centroids having
+    /// coincided with another, or come within the tolerance of the
+    /// diagram.
+    pub merged: usize,
+}
+
+impl<T> Debug for Relaxation<T>
+where
+    T: CoordFloat + Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Relaxation")
+            .field("voronoi", &self.voronoi)
+            .field("energies", &self.energies)
+            .field("moves", &self.moves)
+            .field("converged", &self.converged)
+            .field("merged", &self.merged)
+            .finish()
+    }
+}
+
+impl<T> Voronoi<T>
+where
+    T: 'static
+        + Bounded
+        + CoordFloat
+        + Default
+        + FloatConst
+        + FromPrimitive
+        + GeoNum
+        + NextAfter
+        + Signed,
+{
+    /// Runs Lloyd's algorithm: moves each site to the centroid of its
+    /// cell and rebuilds the diagram, until no site moves by `tolerance`
+    /// or more, or for `iterations` iterations.
+    ///
+    /// The diagram converges towards a centroidal Voronoi tessellation,
+    /// of cells of similar areas. See [`Voronoi::relax_with_density`].
+    ///
+    /// # Errors
+    ///  When a rebuild fails, see [`Voronoi::with_unit_vectors`].
+    pub fn relax(
+        &self,
+        iterations: usize,
+        tolerance: T,
+    ) -> Result<Relaxation<T>, ConstructionError> {
+        self.relax_with_density(iterations, tolerance, |_| T::one())
+    }
+
+    /// As [`Voronoi::relax`], the centroids weighted by `density`, a
+    /// positive function of a point in degrees.
+    ///
+    /// The cells shrink where the density is high, at a rate of about
+    /// one over the square root of the density.
+    ///
+    /// The diagrams are built with the configuration of this diagram, the
+    /// cells always computed, from the sites which have a cell. Input data
+    /// are not carried over. Centroids within the tolerance of the diagram
+    /// are merged into one site, see [`Relaxation::merged`]. The energy, the sum over the cells of the
+    /// integral of the density times the squared chord to the site, and
+    /// the moves are measured on the sphere given by [`Voronoi::radius`].
+    ///
+    /// # Errors
+    ///  When a rebuild fails, see [`Voronoi::with_unit_vectors`].
+    pub fn relax_with_density(
+        &self,
+        iterations: usize,
+        tolerance: T,
+        density: impl Fn(&Coord<T>) -> T,
+    ) -> Result<Relaxation<T>, ConstructionError> {
+        let mut builder = self.config.clone();
+        builder.structures_set(Structures {
+            polygons: true,
+            ..self.structures()
+        });
+        let sites: Vec<[T; 3]> = self
+            .delaunay
+            .vectors
+            .iter()
+            .enumerate()
+            .filter(|(site, v)| {
+                v[0].is_finite()
+                    && self
+                        .delaunay
+                        .polygons
+                        .get(*site)
+                        .is_none_or(|ring| !ring.is_empty())
+            })
+            .map(|(_, v)| *v)
+            .collect();
+        let mut voronoi = builder.build_from_unit_vectors(&sites)?;
+        let mut merged = sites.len() - voronoi.delaunay.vectors.len();
+
+        let r = self.radius();
+        let mut energies = Vec::with_capacity(iterations);
+        let mut moves = Vec::with_capacity(iterations);
+        let mut converged = false;
+        for _ in 0..iterations {
+            let (centroids, energy) = voronoi.delaunay.centroids(&density);
+            let largest = voronoi
+                .delaunay
+                .vectors
+                .iter()
+                .zip(&centroids)
+                .map(|(a, b)| dot(a, b).min(T::one()).acos())
+                .fold(T::zero(), T::max)
+                * r;
+            energies.push(energy * r * r * r * r);
+            moves.push(largest);
+            voronoi = builder.build_from_unit_vectors(&centroids)?;
+            merged += centroids.len() - voronoi.delaunay.vectors.len();
+            if largest < tolerance {
+                converged = true;
+                break;
+            }
+        }
+        Ok(Relaxation {
+            voronoi,
+            energies,
+            moves,
+            converged,
+            merged,
+        })
+    }
+}
@@ -0,0 +1,66 @@
+use geo::CoordFloat;
+use num_traits::FloatConst;
+
+use d3_geo_rs::data_object::FeatureCollection;
+
+use super::Voronoi;
+
+impl<T> Voronoi<T>
+where
+    T: CoordFloat + FloatConst,
+{
+    /// Returns the edges of the β-skeleton as an annotated feature
+    /// collection, in the style of [`Voronoi::links`].
+    ///
+    /// Only the edges flagged by [`crate::delaunay::Delaunay::beta_skeleton`]
+    /// are returned, each labelled with its source, its target and its
+    /// length.
+    #[must_use]
+    pub fn beta_skeleton_links(&self, beta: T) -> FeatureCollection<T> {
+        self.flagged_links(&self.delaunay.beta_skeleton(beta))
+    }
+
+    /// Returns the edges of the Gabriel graph, the β-skeleton for β = 1,
+    /// see [`Voronoi::beta_skeleton_links`].
+    #[must_use]
+    pub fn gabriel_links(&self) -> FeatureCollection<T> {
+        self.flagged_links(&self.delaunay.gabriel_graph())
+    }
+
+    /// Returns the edges of the relative neighborhood graph, the
+    /// β-skeleton for β = 2, see [`Voronoi::beta_skeleton_links`].
+    #[must_use]
+    pub fn relative_neighborhood_links(&self) -> FeatureCollection<T> {
+        self.flagged_links(&self.delaunay.relative_neighborhood_graph())
+    }
+
+    /// Returns the nearest neighbor digraph: one link from each site to
+    /// its nearest other site, in site order.
+    ///
+    /// Two sites nearest to each other are linked both ways.
+    #[must_use]
+    pub fn nearest_neighbor_links(&self) -> FeatureCollection<T> {
+        let features = self
+            .delaunay
+            .nearest_neighbors()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(a, b)| Some((a, b?)))
+            .map(|(a, b)| self.link(a, b, self.length(a, b)))
+            .collect();
+        FeatureCollection(features)
+    }
+
+    /// The links along the flagged edges, in the order of the edges.
+    fn flagged_links(&self, flags: &[bool]) -> FeatureCollection<T> {
+        let features = self
+            .delaunay
+            .edges
+            .iter()
+            .zip(flags)
+            .filter(|(_, &flag)| flag)
+            .map(|(&(a, b), _)| self.link(a, b, self.length(a, b)))
+            .collect();
+        FeatureCollection(features)
+    }
+}
@@ -0,0 +1,73 @@
+use core::cmp::Ordering;
+
+use geo::CoordFloat;
+
+use d3_geo_rs::data_object::FeatureCollection;
+
+use super::Voronoi;
+
+/// Finds the set holding `i`, compressing the path.
+fn root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+impl<T> Voronoi<T>
+where
+    T: CoordFloat,
+{
+    /// Returns the edges of the minimum spanning tree of the sites, each
+    /// with its length, shortest first.
+    ///
+    /// The tree is made of Delaunay edges, as the geodesic minimum
+    /// spanning tree is a subgraph of the Delaunay triangulation. Lengths
+    /// are measured on the sphere given by [`Voronoi::radius`]. Edges are
+    /// pairs of site indices, lower index first.
+    #[must_use]
+    pub fn minimum_spanning_tree(&self) -> Vec<(usize, usize, T)> {
+        let mut edges: Vec<(usize, usize, T)> = self
+            .delaunay
+            .edges
+            .iter()
+            .map(|&(a, b)| (a, b, self.length(a, b)))
+            .collect();
+        edges.sort_unstable_by(|x, y| {
+            x.2.partial_cmp(&y.2)
+                .unwrap_or(Ordering::Equal)
+                .then((x.0, x.1).cmp(&(y.0, y.1)))
+        });
+
+        // Kruskal's algorithm.
+        let mut parent: Vec<usize> = (0..self.points.len()).collect();
+        edges
+            .into_iter()
+            .filter(|&(a, b, _)| {
+                let (a, b) = (root(&mut parent, a), root(&mut parent, b));
+                if a == b {
+                    return false;
+                }
+                parent[a] = b;
+                true
+            })
+            .collect()
+    }
+
+    /// Returns the edges of the minimum spanning tree as an annotated
+    /// feature collection, in the style of [`Voronoi::links`].
+    ///
+    /// Each edge is labelled with its source, its target and its length.
+    /// When the diagram was constructed from features, the properties of
+    /// the source feature and then the target feature are appended.
+    #[must_use]
+    pub fn minimum_spanning_tree_links(&self) -> FeatureCollection<T> {
+        let features = self
+            .minimum_spanning_tree()
+            .into_iter()
+            .map(|(a, b, length)| self.link(a, b, length))
+            .collect();
+        FeatureCollection(features)
+    }
+}
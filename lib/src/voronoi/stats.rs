@@ -0,0 +1,38 @@
+use geo::CoordFloat;
+use num_traits::FloatConst;
+
+use super::CellStats;
+use super::Voronoi;
+
+impl<T> Voronoi<T>
+where
+    T: CoordFloat + FloatConst,
+{
+    /// Returns the size and shape of the cell of each site, in site order,
+    /// as the features of [`Voronoi::polygons`].
+    ///
+    /// Areas and lengths are measured on the sphere given by
+    /// [`Voronoi::radius`], so with a radius in kilometers areas are in
+    /// square kilometers. None for a site without a cell. See
+    /// [`crate::delaunay::Delaunay::cell_stats`].
+    ///
+    /// `FeatureProperty` has no entries for these, so they are not
+    /// appended to the features; zip the two instead.
+    #[must_use]
+    pub fn cell_stats(&self) -> Vec<Option<CellStats<T>>> {
+        let r = self.radius();
+        self.delaunay
+            .cell_stats()
+            .into_iter()
+            .map(|stats| {
+                stats.map(|s| CellStats {
+                    area: s.area * r * r,
+                    perimeter: s.perimeter * r,
+                    inscribed_radius: s.inscribed_radius * r,
+                    circumscribed_radius: s.circumscribed_radius * r,
+                    ..s
+                })
+            })
+            .collect()
+    }
+}
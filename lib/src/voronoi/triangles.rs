@@ -93,4 +93,17 @@ where
 
         FeatureCollection(features)
     }
+
+    /// The sites at the vertices of each triangle, in the order of
+    /// [`Voronoi::triangles`].
+    ///
+    /// The features of the sites are given by [`Voronoi::feature`].
+    pub fn triangle_sites(&self) -> Vec<[usize; 3]> {
+        self.delaunay
+            .triangles
+            .iter()
+            .filter(|tri| excess(&tri.map(|i| self.points[i])) > T::zero())
+            .copied()
+            .collect()
+    }
 }
@@ -0,0 +1,162 @@
+#![allow(clippy::pedantic)]
+
+extern crate pretty_assertions;
+
+use geo::Geometry;
+use geo_types::Coord;
+use pretty_assertions::assert_eq;
+
+use d3_geo_rs::data_object::FeatureCollection;
+use d3_geo_rs::data_object::FeatureProperty;
+use d3_geo_voronoi_rs::apollonius::Apollonius;
+use d3_geo_voronoi_rs::error::ConstructionError;
+use d3_geo_voronoi_rs::error::Rejection;
+use d3_geo_voronoi_rs::error::WeightError;
+
+fn distance(a: &Coord<f64>, b: &Coord<f64>) -> f64 {
+    let (a0, a1) = (a.x.to_radians(), a.y.to_radians());
+    let (b0, b1) = (b.x.to_radians(), b.y.to_radians());
+    (a1.sin() * b1.sin() + a1.cos() * b1.cos() * (b0 - a0).cos())
+        .clamp(-1f64, 1f64)
+        .acos()
+}
+
+fn sites() -> Vec<Coord<f64>> {
+    vec![
+        Coord { x: 0f64, y: 0f64 },
+        Coord { x: 30f64, y: 0f64 },
+        Coord { x: 0f64, y: 40f64 },
+        Coord {
+            x: -20f64,
+            y: -10f64,
+        },
+        Coord {
+            x: 150f64,
+            y: 20f64,
+        },
+    ]
+}
+
+#[test]
+fn boundaries_are_equidistant_from_the_caps() {
+    println!("Each boundary point is as close to two caps, and no closer to any other.");
+    let sites = sites();
+    let radii = [0.3f64, 0.05, 0.1, 0f64, 0.2];
+    let a = match Apollonius::try_with_resolution(&sites, &radii, 32) {
+        Ok(a) => a,
+        Err(e) => {
+            panic!("could not proceed {e}");
+        }
+    };
+    assert!(a.covered().is_empty());
+    for (i, cell) in a.polygons.iter().enumerate() {
+        assert_eq!(cell.len(), 32);
+        for x in cell {
+            let own = distance(x, &sites[i]) - radii[i];
+            let closest = (0..sites.len())
+                .filter(|&j| j != i)
+                .map(|j| distance(x, &sites[j]) - radii[j])
+                .fold(f64::INFINITY, f64::min);
+            assert!((own - closest).abs() < 1e-9);
+        }
+    }
+    assert!(a.neighbors[0].contains(&1));
+}
+
+#[test]
+fn large_caps_claim_more_territory() {
+    let sites = vec![
+        Coord { x: 0f64, y: 0f64 },
+        Coord { x: 40f64, y: 0f64 },
+        Coord { x: 0f64, y: 60f64 },
+        Coord { x: 0f64, y: -60f64 },
+    ];
+    let a = Apollonius::try_new(&sites, &[0.3f64, 0f64, 0f64, 0f64]).unwrap();
+    // Between the first two sites, the boundary is 20 + 0.3 / 2 radians
+    // from the first.
+    let expected = 20f64.to_radians() + 0.15f64;
+    let furthest = a.polygons[0]
+        .iter()
+        .filter(|x| x.y.abs() < 1e-9 && x.x > 0f64)
+        .map(|x| distance(x, &sites[0]))
+        .next()
+        .unwrap();
+    assert!((furthest - expected).abs() < 1e-9);
+}
+
+#[test]
+fn covered_sites_have_empty_cells() {
+    println!("A cap inside another cap gets an empty cell.");
+    let mut sites = sites();
+    sites.push(Coord { x: 2f64, y: 1f64 });
+    sites.push(Coord {
+        x: f64::NAN,
+        y: 1f64,
+    });
+    // A copy of the first site, which keeps its cell.
+    sites.push(sites[0]);
+    let radii = [0.3f64, 0.05, 0.1, 0f64, 0.2, 0.1, 0f64, 0.3];
+    let a = Apollonius::try_new(&sites, &radii).unwrap();
+
+    assert_eq!(a.covered(), &[5, 7]);
+    assert_eq!(a.rejected(), &[(6, Rejection::NonFinite)]);
+    assert!(!a.polygons[0].is_empty());
+    assert!(a.neighbors.iter().all(|n| !n.contains(&5)));
+
+    let FeatureCollection(features) = a.polygons();
+    assert_eq!(features.len(), sites.len());
+    assert_eq!(features[1].properties[0], FeatureProperty::Site(sites[1]));
+    match &features[5].geometry[0] {
+        Geometry::Polygon(polygon) => {
+            assert!(polygon.exterior().0.is_empty());
+        }
+        _ => {
+            panic!("Expected a polygon object.");
+        }
+    }
+    match &features[0].geometry[0] {
+        Geometry::Polygon(polygon) => {
+            let ring = &polygon.exterior().0;
+            assert_eq!(ring.first(), ring.last());
+        }
+        _ => {
+            panic!("Expected a polygon object.");
+        }
+    }
+}
+
+#[test]
+fn rejects_invalid_radii() {
+    let sites = sites();
+    let error = |radii: &[f64]| match Apollonius::try_new(&sites, radii) {
+        Ok(_) => {
+            panic!("Was expecting a weight error.");
+        }
+        Err(e) => e,
+    };
+    assert_eq!(
+        error(&[0f64; 4]),
+        WeightError::CountMismatch {
+            sites: 5,
+            weights: 4
+        }
+    );
+    assert_eq!(
+        error(&[0f64, -1f64, 0f64, 0f64, 0f64]),
+        WeightError::InvalidWeight { site: 1 }
+    );
+    match Apollonius::try_new(&sites[..1], &[0f64]) {
+        Ok(_) => {
+            panic!("Was expecting a weight error.");
+        }
+        Err(e) => {
+            assert_eq!(
+                e,
+                WeightError::Construction(ConstructionError::TooFewSites {
+                    got: 1,
+                    needed: 2
+                })
+            );
+        }
+    }
+}
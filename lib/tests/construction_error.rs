@@ -0,0 +1,121 @@
+#![allow(clippy::pedantic)]
+
+extern crate pretty_assertions;
+
+use geo::Geometry;
+use geo::MultiPoint;
+use geo::Point;
+use geo_types::Coord;
+use pretty_assertions::assert_eq;
+
+use d3_geo_voronoi_rs::delaunay::Delaunay;
+use d3_geo_voronoi_rs::error::ConstructionError;
+use d3_geo_voronoi_rs::voronoi::Voronoi;
+
+fn voronoi_error(sites: Vec<Point<f64>>) -> ConstructionError {
+    match Voronoi::try_from(Geometry::MultiPoint(MultiPoint(sites))) {
+        Ok(_) => {
+            panic!("Was expecting a construction error.");
+        }
+        Err(e) => e,
+    }
+}
+
+#[test]
+fn unsupported_geometry() {
+    let data = Geometry::Point(Point::new(0f64, 0f64));
+    match Voronoi::try_from(data) {
+        Ok(_) => {
+            panic!("Was expecting a construction error.");
+        }
+        Err(e) => {
+            assert_eq!(
+                e,
+                ConstructionError::UnsupportedGeometry { kind: "Point" }
+            );
+            assert_eq!(e.to_string(), "cannot extract sites from a Point");
+        }
+    }
+}
+
+#[test]
+fn too_few_sites() {
+    assert_eq!(
+        voronoi_error(vec![Point::new(0f64, 0f64)]),
+        ConstructionError::TooFewSites { got: 1, needed: 2 }
+    );
+    assert_eq!(
+        voronoi_error(vec![]),
+        ConstructionError::TooFewSites { got: 0, needed: 2 }
+    );
+    assert_eq!(
+        voronoi_error(vec![
+            Point::new(f64::NAN, 0f64),
+            Point::new(5f64, 10f64)
+        ]),
+        ConstructionError::TooFewSites { got: 1, needed: 2 }
+    );
+}
+
+#[test]
+fn all_sites_non_finite() {
+    println!("geoVoronoi.polygons([no valid site]) is rejected.");
+    assert_eq!(
+        voronoi_error(vec![
+            Point::new(f64::NAN, -1f64),
+            Point::new(4f64, f64::NAN),
+            Point::new(f64::INFINITY, 10f64),
+        ]),
+        ConstructionError::AllSitesNonFinite
+    );
+}
+
+#[test]
+fn all_sites_coincident() {
+    assert_eq!(
+        voronoi_error(vec![
+            Point::new(5f64, 10f64),
+            Point::new(5f64, 10f64),
+            Point::new(5f64, 10f64),
+        ]),
+        ConstructionError::AllSitesCoincident
+    );
+}
+
+#[test]
+fn sites_on_a_great_circle() {
+    println!("Three sites on the equator still span a triangulation.");
+    let sites = MultiPoint(vec![
+        Point::new(0f64, 0f64),
+        Point::new(10f64, 0f64),
+        Point::new(20f64, 0f64),
+    ]);
+    match Voronoi::try_from(Geometry::MultiPoint(sites)) {
+        Ok(voronoi) => {
+            assert_eq!(voronoi.polygons().0.len(), 3);
+        }
+        Err(e) => {
+            panic!("could not proceed {e}");
+        }
+    }
+}
+
+#[test]
+fn delaunay_reports_the_same_errors() {
+    let points = vec![Coord { x: 0f64, y: 0f64 }];
+    match Delaunay::try_from(&points) {
+        Ok(_) => {
+            panic!("Was expecting a construction error.");
+        }
+        Err(e) => {
+            assert_eq!(e, ConstructionError::TooFewSites { got: 1, needed: 2 });
+        }
+    }
+}
+
+#[test]
+fn is_a_std_error() {
+    let e: Box<dyn std::error::Error> =
+        Box::new(ConstructionError::AllSitesCoincident);
+    assert_eq!(e.to_string(), "every site is at the same location");
+}
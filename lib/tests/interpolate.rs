@@ -0,0 +1,211 @@
+#![allow(clippy::pedantic)]
+
+extern crate pretty_assertions;
+
+use geo_types::Coord;
+use pretty_assertions::assert_eq;
+
+use d3_geo_voronoi_rs::error::InterpolationError;
+use d3_geo_voronoi_rs::interpolate::Linear;
+use d3_geo_voronoi_rs::interpolate::Sibson;
+use d3_geo_voronoi_rs::voronoi::Voronoi;
+
+fn sites() -> Vec<Coord<f64>> {
+    vec![
+        Coord { x: 0f64, y: 0f64 },
+        Coord { x: 80f64, y: 10f64 },
+        Coord {
+            x: 170f64,
+            y: -5f64,
+        },
+        Coord { x: -95f64, y: 3f64 },
+        Coord { x: 20f64, y: 85f64 },
+        Coord {
+            x: -40f64,
+            y: -80f64,
+        },
+        Coord {
+            x: 120f64,
+            y: 40f64,
+        },
+        Coord {
+            x: -150f64,
+            y: -35f64,
+        },
+    ]
+}
+
+fn voronoi() -> Voronoi<f64> {
+    match Voronoi::from_coords(&sites()) {
+        Ok(voronoi) => voronoi,
+        Err(e) => {
+            panic!("could not proceed {e}");
+        }
+    }
+}
+
+#[test]
+fn sibson_interpolates_between_sites() {
+    println!("The interpolant matches the values at the sites.");
+    let voronoi = voronoi();
+    let values = [1f64, 2f64, 3f64, 4f64, 5f64, 6f64, 7f64, 8f64];
+    let sibson = match Sibson::try_new(&voronoi, &values) {
+        Ok(sibson) => sibson,
+        Err(e) => {
+            panic!("could not proceed {e}");
+        }
+    };
+    for (site, p) in sites().iter().enumerate() {
+        assert_eq!(sibson.coordinates(p), Some(vec![(site, 1f64)]));
+        assert_eq!(sibson.value(p), Some(values[site]));
+    }
+
+    let p = Coord { x: 30f64, y: 20f64 };
+    let coordinates = sibson.coordinates(&p).unwrap();
+    assert!(coordinates.len() > 1);
+    assert!(coordinates.iter().all(|c| c.1 > 0f64));
+    let sum: f64 = coordinates.iter().map(|c| c.1).sum();
+    assert!((sum - 1f64).abs() < 1e-12);
+    // Closer to the first site than to any other.
+    let first = coordinates.iter().find(|c| c.0 == 0).unwrap().1;
+    assert!(coordinates.iter().all(|c| c.1 <= first));
+
+    let value = sibson.value(&p).unwrap();
+    assert!(value > 1f64 && value < 8f64);
+    let points = [
+        p,
+        Coord {
+            x: f64::NAN,
+            y: 0f64,
+        },
+    ];
+    assert_eq!(sibson.values(&points), vec![Some(value), None]);
+}
+
+#[test]
+fn sibson_reproduces_constants() {
+    let voronoi = voronoi();
+    let sibson = Sibson::try_new(&voronoi, &[3f64; 8]).unwrap();
+    for x in [-170f64, -100f64, -10f64, 45f64, 135f64] {
+        for y in [-60f64, -20f64, 15f64, 70f64] {
+            let value = sibson.value(&Coord { x, y }).unwrap();
+            assert!((value - 3f64).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn sibson_needs_one_value_per_site() {
+    let voronoi = voronoi();
+    match Sibson::try_new(&voronoi, &[0f64; 3]) {
+        Ok(_) => {
+            panic!("Was expecting an interpolation error.");
+        }
+        Err(e) => {
+            assert_eq!(
+                e,
+                InterpolationError::ValueCountMismatch {
+                    sites: 8,
+                    values: 3
+                }
+            );
+        }
+    }
+}
+
+#[test]
+fn linear_interpolates_over_triangles() {
+    println!("The interpolant matches the values at the sites.");
+    let voronoi = voronoi();
+    let values = [1f64, 2f64, 3f64, 4f64, 5f64, 6f64, 7f64, 8f64];
+    let linear = match Linear::try_new(&voronoi, &values) {
+        Ok(linear) => linear,
+        Err(e) => {
+            panic!("could not proceed {e}");
+        }
+    };
+    for (site, p) in sites().iter().enumerate() {
+        let value = linear.value(p).unwrap();
+        assert!((value - values[site]).abs() < 1e-12);
+    }
+    let p = Coord { x: 30f64, y: 20f64 };
+    let value = linear.value(&p).unwrap();
+    assert!(value > 1f64 && value < 8f64);
+    let points = [
+        p,
+        Coord {
+            x: f64::NAN,
+            y: 0f64,
+        },
+    ];
+    assert_eq!(linear.values(&points), vec![Some(value), None]);
+
+    let constant = Linear::try_new(&voronoi, &[3f64; 8]).unwrap();
+    for value in constant.values(&sites()) {
+        assert!((value.unwrap() - 3f64).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn linear_fills_a_grid() {
+    println!("Grid cells are evaluated at their centers, from the north west corner.");
+    let voronoi = voronoi();
+    let values = [1f64, 2f64, 3f64, 4f64, 5f64, 6f64, 7f64, 8f64];
+    let linear = Linear::try_new(&voronoi, &values).unwrap();
+    let (width, height) = (12, 6);
+    let grid = linear.grid(width, height);
+    assert_eq!(grid.len(), width * height);
+    for j in 0..height {
+        for i in 0..width {
+            let p = Coord {
+                x: -165f64 + 30f64 * i as f64,
+                y: 75f64 - 30f64 * j as f64,
+            };
+            let expected = linear.value(&p).unwrap();
+            assert!((grid[j * width + i].unwrap() - expected).abs() < 1e-12);
+        }
+    }
+    assert!(linear.grid(0, 10).is_empty());
+}
+
+#[test]
+fn values_per_input_are_collapsed_onto_the_sites() {
+    println!("Merged stations contribute the average of their values.");
+    let mut inputs = sites();
+    // A duplicate of the second station, and a station without a site.
+    inputs.insert(2, inputs[1]);
+    inputs.push(Coord {
+        x: f64::NAN,
+        y: 0f64,
+    });
+    let voronoi = match Voronoi::from_coords(&inputs) {
+        Ok(voronoi) => voronoi,
+        Err(e) => {
+            panic!("could not proceed {e}");
+        }
+    };
+    let values = [1f64, 2f64, 4f64, 3f64, 4f64, 5f64, 6f64, 7f64, 8f64, 100f64];
+    let per_site = [1f64, 3f64, 3f64, 4f64, 5f64, 6f64, 7f64, 8f64];
+
+    let sibson = Sibson::from_inputs(&voronoi, &values).unwrap();
+    let linear = Linear::from_inputs(&voronoi, &values).unwrap();
+    for (site, p) in sites().iter().enumerate() {
+        assert_eq!(sibson.value(p), Some(per_site[site]));
+        assert!((linear.value(p).unwrap() - per_site[site]).abs() < 1e-12);
+    }
+
+    match Linear::from_inputs(&voronoi, &per_site) {
+        Ok(_) => {
+            panic!("Was expecting an interpolation error.");
+        }
+        Err(e) => {
+            assert_eq!(
+                e,
+                InterpolationError::InputCountMismatch {
+                    inputs: 10,
+                    values: 8
+                }
+            );
+        }
+    }
+}
@@ -0,0 +1,221 @@
+#![allow(clippy::pedantic)]
+
+extern crate pretty_assertions;
+
+use geo::Geometry;
+use geo_types::Coord;
+use pretty_assertions::assert_eq;
+
+use d3_geo_rs::data_object::FeatureCollection;
+use d3_geo_rs::data_object::FeatureProperty;
+use d3_geo_voronoi_rs::error::ConstructionError;
+use d3_geo_voronoi_rs::error::Rejection;
+use d3_geo_voronoi_rs::error::WeightError;
+use d3_geo_voronoi_rs::power::PowerDiagram;
+
+fn octahedron() -> Vec<Coord<f64>> {
+    vec![
+        Coord { x: 0f64, y: 0f64 },
+        Coord { x: 90f64, y: 0f64 },
+        Coord { x: 180f64, y: 0f64 },
+        Coord { x: -90f64, y: 0f64 },
+        Coord { x: 0f64, y: 90f64 },
+        Coord { x: 0f64, y: -90f64 },
+    ]
+}
+
+fn cartesian(c: &Coord<f64>) -> [f64; 3] {
+    let (lambda, phi) = (c.x.to_radians(), c.y.to_radians());
+    [
+        phi.cos() * lambda.cos(),
+        phi.cos() * lambda.sin(),
+        phi.sin(),
+    ]
+}
+
+fn power(sites: &[Coord<f64>], weights: &[f64]) -> PowerDiagram<f64> {
+    match PowerDiagram::try_new(sites, weights) {
+        Ok(power) => power,
+        Err(e) => {
+            panic!("could not proceed {e}");
+        }
+    }
+}
+
+#[test]
+fn equal_weights_give_the_voronoi_diagram() {
+    println!("The octahedron's cells are the faces of a cube.");
+    let sites = octahedron();
+    let p = power(&sites, &[0.2f64; 6]);
+
+    assert_eq!(p.triangles.len(), 8);
+    assert_eq!(p.edges.len(), 12);
+    assert!(p.vanished().is_empty());
+    let corner = 1f64 / 3f64.sqrt();
+    for c in &p.centers {
+        let v = cartesian(c);
+        assert!(v.iter().all(|x| (x.abs() - corner).abs() < 1e-9));
+    }
+    for (i, cell) in p.polygons.iter().enumerate() {
+        assert_eq!(cell.len(), 4);
+        assert_eq!(p.neighbors[i].len(), 4);
+        // Clockwise, seen from outside, as the unweighted cells.
+        let s = cartesian(&sites[i]);
+        for k in 0..4 {
+            let a = cartesian(&p.centers[cell[k]]);
+            let b = cartesian(&p.centers[cell[(k + 1) % 4]]);
+            let n = [
+                a[1] * b[2] - a[2] * b[1],
+                a[2] * b[0] - a[0] * b[2],
+                a[0] * b[1] - a[1] * b[0],
+            ];
+            assert!(s[0] * n[0] + s[1] * n[1] + s[2] * n[2] < 0f64);
+        }
+    }
+
+    let FeatureCollection(features) = p.polygons();
+    assert_eq!(features.len(), 6);
+    assert_eq!(features[4].properties[0], FeatureProperty::Site(sites[4]));
+    match &features[0].geometry[0] {
+        Geometry::Polygon(polygon) => {
+            assert_eq!(polygon.exterior().0.len(), 5);
+        }
+        _ => {
+            panic!("Expected a polygon object.");
+        }
+    }
+    assert_eq!(p.cell_mesh().0.len(), 12);
+    let FeatureCollection(links) = p.links();
+    for l in links {
+        match l.properties[2] {
+            FeatureProperty::Length(length) => {
+                assert!((length - 90f64.to_radians()).abs() < 1e-9);
+            }
+            _ => {
+                panic!("Did not find a length property.");
+            }
+        }
+    }
+    let FeatureCollection(triangles) = p.triangles();
+    assert_eq!(triangles.len(), 8);
+}
+
+#[test]
+fn heavier_sites_claim_more_territory() {
+    let sites = octahedron();
+    let p = power(&sites, &[0.6f64, 0.2, 0.2, 0.2, 0.2, 0.2]);
+
+    // The vertices of the heavy cell move away from its site.
+    let unweighted = (1f64 / 3f64.sqrt()).acos();
+    for &c in &p.polygons[0] {
+        let v = cartesian(&p.centers[c]);
+        assert!(v[0].acos() > unweighted + 1e-3);
+    }
+    // Into its neighbor's cell.
+    for &c in p.polygons[1].iter().filter(|c| p.polygons[0].contains(c)) {
+        let v = cartesian(&p.centers[c]);
+        assert!(v[1].acos() < unweighted - 1e-3);
+    }
+}
+
+#[test]
+fn reports_vanished_cells() {
+    println!("A light site close to heavy sites has no cell.");
+    let mut sites = octahedron();
+    sites.push(Coord { x: 10f64, y: 10f64 });
+    sites.push(Coord {
+        x: f64::NAN,
+        y: 10f64,
+    });
+    let mut weights = vec![1f64; 6];
+    weights.push(0f64);
+    weights.push(0f64);
+
+    let p = power(&sites, &weights);
+    assert_eq!(p.vanished(), &[6]);
+    assert_eq!(p.rejected(), &[(7, Rejection::NonFinite)]);
+    assert!(p.polygons[6].is_empty() && p.polygons[7].is_empty());
+    assert!(p.neighbors.iter().all(|n| !n.contains(&6)));
+    let FeatureCollection(features) = p.polygons();
+    assert_eq!(features.len(), 8);
+}
+
+#[test]
+fn reports_duplicate_sites_as_vanished() {
+    println!("Of two sites with one location and weight, one has the cell.");
+    let mut sites = octahedron();
+    sites.push(sites[2]);
+    let weights = vec![0.1f64; 7];
+
+    let p = power(&sites, &weights);
+    assert_eq!(p.vanished().len(), 1);
+    let (kept, lost) = if p.vanished() == [2] { (6, 2) } else { (2, 6) };
+    assert_eq!(p.vanished(), &[lost]);
+    assert!(p.rejected().is_empty());
+    assert!(p.polygons[lost].is_empty());
+    assert_eq!(p.polygons[kept].len(), 4);
+    assert!(p.neighbors.iter().all(|n| !n.contains(&lost)));
+}
+
+#[test]
+fn rejects_invalid_weights() {
+    let sites = octahedron();
+    let error = |weights: &[f64]| match PowerDiagram::try_new(&sites, weights) {
+        Ok(_) => {
+            panic!("Was expecting a weight error.");
+        }
+        Err(e) => e,
+    };
+    assert_eq!(
+        error(&[0f64; 5]),
+        WeightError::CountMismatch {
+            sites: 6,
+            weights: 5
+        }
+    );
+    assert_eq!(
+        error(&[0f64, 0f64, -0.1f64, 0f64, 0f64, 0f64]),
+        WeightError::InvalidWeight { site: 2 }
+    );
+    assert_eq!(
+        error(&[0f64, 0f64, 0f64, 2f64, 0f64, 0f64]),
+        WeightError::InvalidWeight { site: 3 }
+    );
+    assert_eq!(
+        error(&[0f64, 0f64, 0f64, 0f64, f64::NAN, 0f64]),
+        WeightError::InvalidWeight { site: 4 }
+    );
+}
+
+#[test]
+fn rejects_degenerate_input() {
+    let sites = octahedron();
+    match PowerDiagram::try_new(&sites[..3], &[0f64; 3]) {
+        Ok(_) => {
+            panic!("Was expecting a weight error.");
+        }
+        Err(e) => {
+            assert_eq!(
+                e,
+                WeightError::Construction(ConstructionError::TooFewSites {
+                    got: 3,
+                    needed: 4
+                })
+            );
+        }
+    }
+    // Four sites on the equator.
+    match PowerDiagram::try_new(&sites[..4], &[0f64; 4]) {
+        Ok(_) => {
+            panic!("Was expecting a weight error.");
+        }
+        Err(e) => {
+            assert_eq!(
+                e,
+                WeightError::Construction(
+                    ConstructionError::DegenerateTriangulation
+                )
+            );
+        }
+    }
+}
@@ -5,15 +5,24 @@ extern crate pretty_assertions;
 use geo::algorithm::cyclic_match::CyclicMatch;
 use geo::coords_iter::CoordsIter;
 use geo::line_string;
+use geo::polygon;
+use geo::Contains;
 use geo::Geometry;
 use geo::LineString;
 use geo::MultiPoint;
+use geo::MultiPolygon;
 use geo::Point;
+use geo::Polygon;
 use geo_types::Coord;
 use pretty_assertions::assert_eq;
 
 use d3_geo_rs::data_object::FeatureCollection;
 use d3_geo_rs::data_object::FeatureProperty;
+use d3_geo_rs::data_object::Features;
+use d3_geo_voronoi_rs::delaunay::Structures;
+use d3_geo_voronoi_rs::voronoi::Rejection;
+use d3_geo_voronoi_rs::voronoi::Units;
+use d3_geo_voronoi_rs::voronoi::UpdateError;
 use d3_geo_voronoi_rs::voronoi::Voronoi;
 
 #[test]
@@ -121,6 +130,32 @@ pub fn polygons_returns_polygons() {
             panic!("Expected a polygon object.");
         }
     }
+
+    // The cells of a cube are squares, their rings closed once.
+    let gv = match Voronoi::from_coords(&[
+        Coord { x: 0f64, y: 0f64 },
+        Coord { x: 90f64, y: 0f64 },
+        Coord { x: 180f64, y: 0f64 },
+        Coord { x: -90f64, y: 0f64 },
+        Coord { x: 0f64, y: 90f64 },
+        Coord { x: 0f64, y: -90f64 },
+    ]) {
+        Ok(ok) => ok,
+        Err(_) => {
+            panic!("could not proceed");
+        }
+    };
+    let FeatureCollection(features) = gv.polygons();
+    assert_eq!(features.len(), 6);
+    for feature in &features {
+        let Geometry::Polygon(polygon) = &feature.geometry[0] else {
+            panic!("Expected a polygon object.");
+        };
+        let ring = &polygon.exterior().0;
+        assert_eq!(ring.len(), 5);
+        assert_eq!(ring[0], ring[4]);
+        assert!(ring.windows(2).all(|w| w[0] != w[1]));
+    }
 }
 #[test]
 fn polygon_tolerates_nan() {
@@ -145,6 +180,26 @@ fn polygon_tolerates_nan() {
         }
     };
     let _u = gv.polygons();
+
+    // The rejected sites are reported, the others map onto their cell.
+    assert_eq!(
+        gv.rejected(),
+        &[(2, Rejection::NonFinite), (3, Rejection::NonFinite)]
+    );
+    assert_eq!(gv.site_index(4), Some(2));
+    assert_eq!(gv.inputs(2), &[4]);
+    let by_input = gv.polygons_by_input();
+    assert_eq!(by_input.len(), 5);
+    assert!(by_input[2].is_none() && by_input[3].is_none());
+    match &by_input[4] {
+        Some(f) => assert_eq!(
+            f.properties[0],
+            FeatureProperty::Site(Coord { x: 5f64, y: 10f64 })
+        ),
+        None => {
+            panic!("Was expecting the cell of the last site.");
+        }
+    }
 }
 
 // it("geoVoronoi.polygons([no valid site]) returns an empty collection.", () => {
@@ -164,13 +219,82 @@ fn polygon_tolerates_nan() {
 //     assert.deepStrictEqual(geoVoronoi.geoVoronoi().links(sites).features.map(function(d) { return d.properties.urquhart; }), [ false, true, true ]);
 //   });
 
-// it("geoVoronoi.x() changes accessor.", () => {
-//     const sites = [{lon:10,lat:0}, {lon:3, lat:5}, {lon:-2, lat:5}];
-//     assert.deepStrictEqual(
-//       geoVoronoi.geoVoronoi().x(d => +d.lon).y(d => +d.lat) (sites).points,
-//       [ [ 10, 0 ], [ 3, 5 ], [ -2, 5 ] ]
-//     );
-//   });
+fn site_coordinates(fc: FeatureCollection<f64>) -> Vec<Coord<f64>> {
+    fc.0.iter()
+        .map(|f| {
+            f.properties
+                .iter()
+                .find_map(|p| match p {
+                    FeatureProperty::Sitecoordinates(c) => Some(*c),
+                    _ => None,
+                })
+                .expect("Did not find a sitecoordinates property.")
+        })
+        .collect()
+}
+
+fn square(x: f64, y: f64) -> Polygon<f64> {
+    polygon![
+        (x: x, y: y),
+        (x: x + 1., y: y),
+        (x: x + 1., y: y + 1.),
+        (x: x, y: y + 1.),
+    ]
+}
+
+#[test]
+fn x_changes_accessor() {
+    println!("geoVoronoi.x() changes accessor.");
+    // The site is the first vertex of each square, rather than its centroid.
+    let first = |d: &Geometry<f64>| match d {
+        Geometry::Polygon(p) => p.exterior().0[0],
+        _ => {
+            panic!("Expected a polygon object.");
+        }
+    };
+    let mut builder = Voronoi::builder();
+    builder
+        .x_set(move |d: &Geometry<f64>| first(d).x)
+        .y_set(move |d: &Geometry<f64>| first(d).y);
+
+    let sites =
+        MultiPolygon(vec![square(10., 0.), square(3., 5.), square(-2., 5.)]);
+    let gv = match builder.build(Geometry::MultiPolygon(sites)) {
+        Ok(gv) => gv,
+        Err(_) => {
+            panic!("could not proceed");
+        }
+    };
+
+    assert_eq!(
+        site_coordinates(gv.polygons()),
+        vec![
+            Coord { x: 10., y: 0. },
+            Coord { x: 3., y: 5. },
+            Coord { x: -2., y: 5. }
+        ]
+    );
+}
+
+#[test]
+fn default_accessor_is_the_centroid() {
+    let sites = vec![square(10., 0.), square(3., 5.), square(-2., 5.)];
+    let gv = match Voronoi::try_from(sites) {
+        Ok(gv) => gv,
+        Err(_) => {
+            panic!("could not proceed");
+        }
+    };
+
+    assert_eq!(
+        site_coordinates(gv.polygons()),
+        vec![
+            Coord { x: 10.5, y: 0.5 },
+            Coord { x: 3.5, y: 5.5 },
+            Coord { x: -1.5, y: 5.5 }
+        ]
+    );
+}
 
 #[test]
 fn computes_the_hull() {
@@ -291,45 +415,785 @@ fn finds_p() {
         Point::new(0f64, 0f64),
     ]);
 
-    let mut voro;
-    match Voronoi::try_from(Geometry::MultiPoint(sites.clone())) {
-        Ok(ok) => voro = ok,
+    let voro = match Voronoi::try_from(Geometry::MultiPoint(sites)) {
+        Ok(ok) => ok,
         Err(_) => {
             panic!("cannot proceed");
         }
     };
 
+    let p = Coord {
+        x: 1.0_f64,
+        y: 1.0_f64,
+    };
+    assert_eq!(voro.find(&p, None), Some(4));
+    assert_eq!(voro.find(&p, Some(4.0)), Some(4));
+    assert_eq!(voro.find(&p, Some(0.001)), None);
+    // The walk ends when it starts at the nearest site.
+    assert_eq!(voro.find_from(&p, Some(4), None), (Some(4), Some(4)));
+    // The nearest site is the hint, even beyond the cutoff.
+    assert_eq!(voro.find_from(&p, Some(1), Some(0.001)), (None, Some(4)));
+
+    let points = vec![
+        Coord { x: 9f64, y: 9f64 },
+        p,
+        Coord {
+            x: f64::NAN,
+            y: 0f64,
+        },
+        Coord { x: -3f64, y: 6f64 },
+    ];
     assert_eq!(
-        voro.find(
-            &Coord {
-                x: 1.0_f64,
-                y: 1.0_f64
-            },
-            None
-        ),
-        Some(4)
+        voro.find_many(&points, None),
+        vec![Some(1), Some(4), None, Some(3)]
     );
-    // TODO bug ... strange bug/hang ... unless I define voro twice.
-    let mut voro2;
-    match Voronoi::try_from(Geometry::MultiPoint(sites)) {
-        Ok(ok) => voro2 = ok,
+}
+
+#[test]
+fn finds_k_nearest() {
+    println!("The nearest sites, sorted by distance.");
+    let sites = vec![
+        Coord { x: 10f64, y: 0f64 },
+        Coord { x: 10f64, y: 10f64 },
+        Coord { x: 3f64, y: 5f64 },
+        Coord { x: -2f64, y: 5f64 },
+        Coord { x: 0f64, y: 0f64 },
+    ];
+    let voro = match Voronoi::from_coords(&sites) {
+        Ok(ok) => ok,
         Err(_) => {
             panic!("cannot proceed");
         }
     };
+    let p = Coord { x: 1f64, y: 1f64 };
+    let distance = |s: &Coord<f64>| {
+        let (a0, a1) = (p.x.to_radians(), p.y.to_radians());
+        let (b0, b1) = (s.x.to_radians(), s.y.to_radians());
+        (a1.sin() * b1.sin() + a1.cos() * b1.cos() * (b0 - a0).cos())
+            .clamp(-1f64, 1f64)
+            .acos()
+    };
 
+    let nearest = voro.k_nearest(&p, 3);
+    let order: Vec<usize> = nearest.iter().map(|n| n.0).collect();
+    assert_eq!(order, vec![4, 2, 3]);
+    for (site, d) in nearest {
+        assert!((d - distance(&sites[site])).abs() < 1e-9);
+    }
+    assert_eq!(voro.k_nearest(&p, 10).len(), 5);
+    assert!(voro.k_nearest(&p, 0).is_empty());
+}
+
+#[test]
+fn finds_sites_and_cells_within_a_cap() {
+    let sites = vec![
+        Coord { x: 10f64, y: 0f64 },
+        Coord { x: 10f64, y: 10f64 },
+        Coord { x: 3f64, y: 5f64 },
+        Coord { x: -2f64, y: 5f64 },
+        Coord { x: 0f64, y: 0f64 },
+    ];
+    let mut builder = Voronoi::builder();
+    builder.radius_set(6371f64);
+    let voro = match builder.build_from_coords(&sites) {
+        Ok(ok) => ok,
+        Err(_) => {
+            panic!("cannot proceed");
+        }
+    };
+    let p = Coord { x: 1f64, y: 1f64 };
+    // About 5.5 degrees, between the third and fourth nearest sites.
+    let within = voro.sites_within(&p, 600f64);
+    let order: Vec<usize> = within.iter().map(|w| w.0).collect();
+    assert_eq!(order, vec![4, 2, 3]);
+    assert!(within.iter().all(|w| w.1 < 600f64));
+    assert!(voro.sites_within(&p, 10f64).is_empty());
+
+    // The cap around p overlaps its own cell, and reaches further cells
+    // before it reaches their sites.
+    assert_eq!(voro.cells_within(&p, 10f64), vec![4]);
+    let cells = voro.cells_within(&p, 600f64);
+    assert!(order.iter().all(|site| cells.contains(site)));
+    assert!(cells.contains(&0));
+}
+
+#[test]
+fn locates_the_triangle_holding_a_point() {
+    println!("Barycentric weights rebuild the point, outside the hull there is no triangle.");
+    let sites = vec![
+        Coord { x: 10f64, y: 0f64 },
+        Coord { x: 10f64, y: 10f64 },
+        Coord { x: 3f64, y: 5f64 },
+        Coord { x: -2f64, y: 5f64 },
+        Coord { x: 0f64, y: 0f64 },
+    ];
+    let voro = match Voronoi::from_coords(&sites) {
+        Ok(ok) => ok,
+        Err(_) => {
+            panic!("cannot proceed");
+        }
+    };
+    let delaunay = &voro.delaunay;
+    let p = Coord { x: 1f64, y: 1f64 };
+    let (t, weights) = delaunay.locate(&p).unwrap();
+    assert!(weights.iter().all(|w| *w >= 0f64));
+    assert!((weights.iter().sum::<f64>() - 1f64).abs() < 1e-12);
+    let vectors = delaunay.triangles[t].map(|i| delaunay.vectors[i]);
+    let v: Vec<f64> = (0..3)
+        .map(|k| (0..3).map(|j| weights[j] * vectors[j][k]).sum())
+        .collect();
+    let (lambda, phi) = (v[1].atan2(v[0]), v[2].atan2(v[0].hypot(v[1])));
+    assert!((lambda.to_degrees() - p.x).abs() < 1e-9);
+    assert!((phi.to_degrees() - p.y).abs() < 1e-9);
+
+    // At a site, all the weight is on that site.
+    let (t, weights) = delaunay.locate(&sites[2]).unwrap();
+    let k = delaunay.triangles[t].iter().position(|&i| i == 2).unwrap();
+    assert!((weights[k] - 1f64).abs() < 1e-12);
+
+    assert_eq!(delaunay.locate(&Coord { x: -50f64, y: 0f64 }), None);
     assert_eq!(
-        voro2.find(
-            &Coord {
-                x: 1.0_f64,
-                y: 1.0_f64
-            },
-            Some(4.0)
-        ),
-        Some(4)
+        delaunay.locate(&Coord {
+            x: f64::NAN,
+            y: 0f64
+        }),
+        None
+    );
+}
+
+#[test]
+fn minimum_spanning_tree() {
+    println!("The shortest edges which connect all the sites.");
+    let sites = vec![
+        Coord { x: 10f64, y: 0f64 },
+        Coord { x: 10f64, y: 10f64 },
+        Coord { x: 3f64, y: 5f64 },
+        Coord { x: -2f64, y: 5f64 },
+        Coord { x: 0f64, y: 0f64 },
+    ];
+    let voro = match Voronoi::from_coords(&sites) {
+        Ok(ok) => ok,
+        Err(_) => {
+            panic!("cannot proceed");
+        }
+    };
+    let tree = voro.minimum_spanning_tree();
+    let edges: Vec<(usize, usize)> = tree.iter().map(|e| (e.0, e.1)).collect();
+    assert_eq!(edges, vec![(2, 3), (3, 4), (1, 2), (0, 2)]);
+    assert!((tree[0].2 - 0.086934177664642f64).abs() < 1e-9);
+
+    let FeatureCollection(links) = voro.minimum_spanning_tree_links();
+    assert_eq!(links.len(), 4);
+    assert_eq!(
+        links[0].properties,
+        vec![
+            FeatureProperty::Source(sites[2]),
+            FeatureProperty::Target(sites[3]),
+            FeatureProperty::Length(tree[0].2),
+        ]
     );
 }
 
+#[test]
+fn proximity_graphs() {
+    println!("The Gabriel, relative neighborhood and nearest neighbor graphs.");
+    let sites = vec![
+        Coord { x: 10f64, y: 0f64 },
+        Coord { x: 10f64, y: 10f64 },
+        Coord { x: 3f64, y: 5f64 },
+        Coord { x: -2f64, y: 5f64 },
+        Coord { x: 0f64, y: 0f64 },
+    ];
+    let voro = match Voronoi::from_coords(&sites) {
+        Ok(ok) => ok,
+        Err(_) => {
+            panic!("cannot proceed");
+        }
+    };
+    let flagged = |flags: Vec<bool>| {
+        let mut edges: Vec<(usize, usize)> = voro
+            .delaunay
+            .edges
+            .iter()
+            .zip(flags)
+            .filter_map(|(e, flag)| flag.then_some(*e))
+            .collect();
+        edges.sort();
+        edges
+    };
+    let gabriel = flagged(voro.delaunay.gabriel_graph());
+    assert_eq!(
+        gabriel,
+        vec![(0, 1), (0, 2), (0, 4), (1, 2), (2, 3), (2, 4), (3, 4)]
+    );
+    assert_eq!(flagged(voro.delaunay.beta_skeleton(1f64)), gabriel);
+    let rng = vec![(0, 2), (1, 2), (2, 3), (3, 4)];
+    assert_eq!(flagged(voro.delaunay.relative_neighborhood_graph()), rng);
+    assert_eq!(flagged(voro.delaunay.beta_skeleton(2f64)), rng);
+    assert_eq!(flagged(voro.delaunay.nearest_neighbor_graph()), rng);
+    assert_eq!(
+        voro.delaunay.nearest_neighbors(),
+        vec![Some(2), Some(2), Some(3), Some(2), Some(3)]
+    );
+
+    let FeatureCollection(links) = voro.gabriel_links();
+    assert_eq!(links.len(), 7);
+    let FeatureCollection(links) = voro.relative_neighborhood_links();
+    assert_eq!(links.len(), 4);
+    let FeatureCollection(links) = voro.nearest_neighbor_links();
+    assert_eq!(links.len(), 5);
+    assert_eq!(links[4].properties[0], FeatureProperty::Source(sites[4]));
+    assert_eq!(links[4].properties[1], FeatureProperty::Target(sites[3]));
+}
+
+#[test]
+fn shortest_paths() {
+    println!("Routes between sites along Delaunay edges.");
+    let sites = vec![
+        Coord { x: 10f64, y: 0f64 },
+        Coord { x: 10f64, y: 10f64 },
+        Coord { x: 3f64, y: 5f64 },
+        Coord { x: -2f64, y: 5f64 },
+        Coord { x: 0f64, y: 0f64 },
+    ];
+    let voro = match Voronoi::from_coords(&sites) {
+        Ok(ok) => ok,
+        Err(_) => {
+            panic!("cannot proceed");
+        }
+    };
+    let path = voro.shortest_path(0, 3).expect("a path");
+    assert_eq!(path.sites, vec![0, 2, 3]);
+    assert!((path.length - 0.236946650937984f64).abs() < 1e-9);
+    assert_eq!(path.geometry.0, vec![sites[0], sites[2], sites[3]]);
+
+    // Around the site 2.
+    let path = voro
+        .shortest_path_by(0, 3, |a, b| {
+            if a == 2 || b == 2 {
+                f64::INFINITY
+            } else {
+                1f64
+            }
+        })
+        .expect("a path");
+    assert_eq!(path.sites.len(), 3);
+    assert!(!path.sites.contains(&2));
+    assert_eq!(path.length, 2f64);
+
+    let path = voro.shortest_path(1, 1).expect("a path");
+    assert_eq!(path.sites, vec![1]);
+    assert_eq!(path.length, 0f64);
+    assert_eq!(voro.shortest_path(1, 5), None);
+
+    assert_eq!(
+        voro.hops(0),
+        vec![Some(0), Some(1), Some(1), Some(2), Some(1)]
+    );
+    assert!(voro.hops(5).is_empty());
+}
+
+#[test]
+fn max_clearance_paths() {
+    println!("Routes along the cell edges, away from the sites.");
+    // The cells are the faces of a cube.
+    let sites = vec![
+        Coord { x: 0f64, y: 0f64 },
+        Coord { x: 90f64, y: 0f64 },
+        Coord { x: 180f64, y: 0f64 },
+        Coord { x: -90f64, y: 0f64 },
+        Coord { x: 0f64, y: 90f64 },
+        Coord { x: 0f64, y: -90f64 },
+    ];
+    let voro = match Voronoi::from_coords(&sites) {
+        Ok(ok) => ok,
+        Err(_) => {
+            panic!("cannot proceed");
+        }
+    };
+    let from = Coord { x: 0f64, y: 10f64 };
+    let to = Coord {
+        x: 180f64,
+        y: -10f64,
+    };
+    let path = voro.max_clearance_path(&from, &to).expect("a path");
+    // From the middle of a cube edge to the middle of the opposite edge.
+    let edge = (1f64 / 3f64).acos();
+    assert!((path.length - 3f64 * edge).abs() < 1e-9);
+    assert!((path.clearance - 45f64.to_radians()).abs() < 1e-9);
+    let first = path.geometry.0[0];
+    let last = path.geometry.0[path.geometry.0.len() - 1];
+    assert!(first.x.abs() < 1e-9 && (first.y - 45f64).abs() < 1e-9);
+    assert!((last.x.abs() - 180f64).abs() < 1e-9);
+    assert!((last.y + 45f64).abs() < 1e-9);
+
+    let path = voro.clearance_path(&from, &to, 0.5f64).expect("a path");
+    assert!((path.length - 3f64 * edge).abs() < 1e-9);
+    assert_eq!(voro.clearance_path(&from, &to, 0.8f64), None);
+}
+
+#[test]
+fn cell_stats() {
+    println!("The size and shape of the cells.");
+    // The cells are the faces of a cube.
+    let sites = vec![
+        Coord { x: 0f64, y: 0f64 },
+        Coord { x: 90f64, y: 0f64 },
+        Coord { x: 180f64, y: 0f64 },
+        Coord { x: -90f64, y: 0f64 },
+        Coord { x: 0f64, y: 90f64 },
+        Coord { x: 0f64, y: -90f64 },
+    ];
+    let mut builder = Voronoi::builder();
+    builder.radius_set(2f64);
+    let voro = match builder.build_from_coords(&sites) {
+        Ok(ok) => ok,
+        Err(_) => {
+            panic!("cannot proceed");
+        }
+    };
+    let stats = voro.cell_stats();
+    assert_eq!(stats.len(), 6);
+    let pi = std::f64::consts::PI;
+    for s in stats {
+        let s = s.expect("a cell");
+        assert!((s.area - 4f64 * 4f64 * pi / 6f64).abs() < 1e-9);
+        assert!(
+            (s.perimeter - 2f64 * 4f64 * (1f64 / 3f64).acos()).abs() < 1e-9
+        );
+        assert_eq!(s.vertices, 4);
+        assert!((s.compactness - 0.904648314850834f64).abs() < 1e-9);
+        assert!((s.inscribed_radius - 2f64 * pi / 4f64).abs() < 1e-9);
+        let corner = (1f64 / 3f64.sqrt()).acos();
+        assert!((s.circumscribed_radius - 2f64 * corner).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn lloyd_relaxation() {
+    println!("Relaxed cells have similar areas.");
+    let sites: Vec<Coord<f64>> = (0..20)
+        .map(|i| Coord {
+            x: (i * 37 % 90) as f64 - 45f64,
+            y: (i * 23 % 60) as f64 - 30f64,
+        })
+        .collect();
+    let voro = match Voronoi::from_coords(&sites) {
+        Ok(ok) => ok,
+        Err(_) => {
+            panic!("cannot proceed");
+        }
+    };
+    let relaxation = match voro.relax(200, 1e-3f64) {
+        Ok(ok) => ok,
+        Err(e) => {
+            panic!("could not proceed {e}");
+        }
+    };
+    assert!(relaxation.converged);
+    assert_eq!(relaxation.merged, 0);
+    assert!(relaxation.moves.len() < 200);
+    assert!(*relaxation.moves.last().unwrap() < 1e-3f64);
+    for w in relaxation.energies.windows(2) {
+        assert!(w[1] <= w[0] + 1e-12);
+    }
+    let areas: Vec<f64> = relaxation
+        .voronoi
+        .cell_stats()
+        .into_iter()
+        .map(|s| s.expect("a cell").area)
+        .collect();
+    assert_eq!(areas.len(), 20);
+    let largest = areas.iter().cloned().fold(0f64, f64::max);
+    let smallest = areas.iter().cloned().fold(f64::INFINITY, f64::min);
+    assert!(largest / smallest < 1.5f64);
+
+    // Smaller cells where the density is higher.
+    let relaxation = match voro.relax_with_density(200, 1e-3f64, |p| {
+        if p.y > 0f64 {
+            8f64
+        } else {
+            1f64
+        }
+    }) {
+        Ok(ok) => ok,
+        Err(e) => {
+            panic!("could not proceed {e}");
+        }
+    };
+    let v = relaxation.voronoi;
+    let stats = v.cell_stats();
+    let north = v
+        .delaunay
+        .points
+        .iter()
+        .zip(&stats)
+        .filter(|(p, _)| p.y > 45f64)
+        .map(|(_, s)| s.expect("a cell").area)
+        .fold(f64::INFINITY, f64::min);
+    let south = v
+        .delaunay
+        .points
+        .iter()
+        .zip(&stats)
+        .filter(|(p, _)| p.y < -45f64)
+        .map(|(_, s)| s.expect("a cell").area)
+        .fold(0f64, f64::max);
+    assert!(north < south);
+}
+
+#[test]
+fn clipped_polygons() {
+    println!("Cells clipped to a polar cap, then to the rest of the sphere.");
+    // The cells are the faces of a cube.
+    let sites = vec![
+        Coord { x: 0f64, y: 0f64 },
+        Coord { x: 90f64, y: 0f64 },
+        Coord { x: 180f64, y: 0f64 },
+        Coord { x: -90f64, y: 0f64 },
+        Coord { x: 0f64, y: 90f64 },
+        Coord { x: 0f64, y: -90f64 },
+    ];
+    let voro = match Voronoi::from_coords(&sites) {
+        Ok(ok) => ok,
+        Err(_) => {
+            panic!("cannot proceed");
+        }
+    };
+    // Clockwise seen from outside, around the north pole.
+    let cap: LineString<f64> = (0..8)
+        .map(|k| Coord {
+            x: 180f64 - 45f64 * f64::from(k),
+            y: 60f64,
+        })
+        .collect();
+    let mask = MultiPolygon(vec![Polygon::new(cap.clone(), vec![])]);
+    let FeatureCollection(features) = voro.polygons_clipped(&mask);
+    assert_eq!(features.len(), 6);
+    for (i, feature) in features.iter().enumerate() {
+        assert_eq!(feature.properties[0], FeatureProperty::Site(sites[i]));
+        let Geometry::MultiPolygon(clipped) = &feature.geometry[0] else {
+            panic!("expected a MultiPolygon");
+        };
+        if i == 4 {
+            assert_eq!(clipped.0.len(), 1);
+            assert_eq!(clipped.0[0].exterior().0.len(), 9);
+            assert!(clipped.0[0].interiors().is_empty());
+        } else {
+            assert!(clipped.0.is_empty());
+        }
+    }
+
+    // Counterclockwise, the mask is all but the cap.
+    let rest: LineString<f64> = cap.0.iter().rev().copied().collect();
+    let mask = MultiPolygon(vec![Polygon::new(rest, vec![])]);
+    let FeatureCollection(features) = voro.polygons_clipped(&mask);
+    assert_eq!(features.len(), 6);
+    for (i, feature) in features.iter().enumerate() {
+        let Geometry::MultiPolygon(clipped) = &feature.geometry[0] else {
+            panic!("expected a MultiPolygon");
+        };
+        assert_eq!(clipped.0.len(), 1);
+        assert_eq!(clipped.0[0].exterior().0.len(), 5);
+        let holes = usize::from(i == 4);
+        assert_eq!(clipped.0[0].interiors().len(), holes);
+    }
+}
+
+#[test]
+fn clipped_polygons_concave_mask() {
+    println!("A concave mask splits a cell, and holes stay apart.");
+    // The cells are the faces of a cube.
+    let sites = vec![
+        Coord { x: 0f64, y: 0f64 },
+        Coord { x: 90f64, y: 0f64 },
+        Coord { x: 180f64, y: 0f64 },
+        Coord { x: -90f64, y: 0f64 },
+        Coord { x: 0f64, y: 90f64 },
+        Coord { x: 0f64, y: -90f64 },
+    ];
+    let voro = match Voronoi::from_coords(&sites) {
+        Ok(ok) => ok,
+        Err(_) => {
+            panic!("cannot proceed");
+        }
+    };
+    let parts = |mask: &MultiPolygon<f64>| -> Vec<Vec<(usize, usize)>> {
+        let FeatureCollection(features) = voro.polygons_clipped(mask);
+        features
+            .iter()
+            .map(|feature| {
+                let Geometry::MultiPolygon(clipped) = &feature.geometry[0]
+                else {
+                    panic!("expected a MultiPolygon");
+                };
+                clipped
+                    .0
+                    .iter()
+                    .map(|p| (p.exterior().0.len(), p.interiors().len()))
+                    .collect()
+            })
+            .collect()
+    };
+    // Clockwise, a U whose base lies in the southern cell and whose arms
+    // reach into the cell of ( 0, 0 ).
+    let u: LineString<f64> = vec![
+        (-30f64, -60f64),
+        (-30f64, 0f64),
+        (-20f64, 0f64),
+        (-20f64, -50f64),
+        (20f64, -50f64),
+        (20f64, 0f64),
+        (30f64, 0f64),
+        (30f64, -60f64),
+    ]
+    .into();
+    let mask = MultiPolygon(vec![Polygon::new(u.clone(), vec![])]);
+    let parts_in = parts(&mask);
+    assert_eq!(parts_in[0], vec![(5, 0), (5, 0)]);
+    assert_eq!(parts_in[5], vec![(9, 0)]);
+    assert!(parts_in[1..5].iter().all(Vec::is_empty));
+
+    // Counterclockwise, the mask is all but the U: the arms bite into the
+    // cell of ( 0, 0 ), and cut the southern cell in two.
+    let rest: LineString<f64> = u.0.iter().rev().copied().collect();
+    let mask = MultiPolygon(vec![Polygon::new(rest, vec![])]);
+    let parts_out = parts(&mask);
+    assert_eq!(parts_out[0], vec![(13, 0)]);
+    assert_eq!(parts_out[5].len(), 2);
+    assert!(parts_out[1..5].iter().all(|p| p == &vec![(5, 0)]));
+
+    // The cell of ( 0, 0 ) reaches beyond the hemisphere centered on its
+    // site, around ( -100, -10 ), and stays in one piece.
+    let sites = vec![
+        Coord { x: 0f64, y: 0f64 },
+        Coord { x: 20f64, y: 0f64 },
+        Coord { x: 0f64, y: 20f64 },
+        Coord { x: 20f64, y: 20f64 },
+        Coord { x: 10f64, y: 10f64 },
+    ];
+    let voro = match Voronoi::from_coords(&sites) {
+        Ok(ok) => ok,
+        Err(_) => {
+            panic!("cannot proceed");
+        }
+    };
+    // Counterclockwise, all but a square around ( -100, -10 ).
+    let square: LineString<f64> = vec![
+        (-105f64, -15f64),
+        (-95f64, -15f64),
+        (-95f64, -5f64),
+        (-105f64, -5f64),
+    ]
+    .into();
+    let mask = MultiPolygon(vec![Polygon::new(square, vec![])]);
+    let FeatureCollection(features) = voro.polygons_clipped(&mask);
+    for (i, feature) in features.iter().enumerate() {
+        let Geometry::MultiPolygon(clipped) = &feature.geometry[0] else {
+            panic!("expected a MultiPolygon");
+        };
+        assert_eq!(clipped.0.len(), 1);
+        assert_eq!(clipped.0[0].interiors().len(), usize::from(i == 0));
+    }
+}
+
+#[test]
+fn planar_output() {
+    println!("Cells split at the antimeridian and closed along the poles.");
+    // The cells are the faces of a cube.
+    let sites = vec![
+        Coord { x: 0f64, y: 0f64 },
+        Coord { x: 90f64, y: 0f64 },
+        Coord { x: 180f64, y: 0f64 },
+        Coord { x: -90f64, y: 0f64 },
+        Coord { x: 0f64, y: 90f64 },
+        Coord { x: 0f64, y: -90f64 },
+    ];
+    let voro = match Voronoi::from_coords(&sites) {
+        Ok(ok) => ok,
+        Err(_) => {
+            panic!("cannot proceed");
+        }
+    };
+    let multi_polygon = |feature: &Features<f64>| match &feature.geometry[0] {
+        Geometry::MultiPolygon(mp) => mp.clone(),
+        _ => panic!("expected a MultiPolygon"),
+    };
+    let FeatureCollection(cells) = voro.polygons_planar();
+    assert_eq!(cells.len(), 6);
+    for (i, feature) in cells.iter().enumerate() {
+        assert_eq!(feature.properties[0], FeatureProperty::Site(sites[i]));
+        let mp = multi_polygon(feature);
+        assert!(mp.coords_iter().all(|c| c.x.abs() <= 180f64));
+        // Across the antimeridian.
+        assert_eq!(mp.0.len(), if i == 2 { 2 } else { 1 });
+    }
+    // Closed along the pole inside the cell, not the opposite one.
+    let north = multi_polygon(&cells[4]);
+    assert!(north.coords_iter().any(|c| c.y == 90f64));
+    assert!(north.coords_iter().all(|c| c.y != -90f64));
+    assert!(north.contains(&Point::new(170f64, 89f64)));
+    assert!(!north.contains(&Point::new(0f64, -80f64)));
+    let south = multi_polygon(&cells[5]);
+    assert!(south.coords_iter().any(|c| c.y == -90f64));
+    assert!(south.coords_iter().all(|c| c.y != 90f64));
+    assert!(south.contains(&Point::new(-170f64, -89f64)));
+    assert!(!south.contains(&Point::new(0f64, 80f64)));
+
+    let FeatureCollection(triangles) = voro.triangles_planar();
+    assert_eq!(triangles.len(), 8);
+    for feature in &triangles {
+        let mp = multi_polygon(feature);
+        assert_eq!(mp.0.len(), 1);
+        assert!(mp.coords_iter().all(|c| c.x.abs() <= 180f64));
+    }
+
+    let sites = vec![
+        Coord { x: 170f64, y: 0f64 },
+        Coord {
+            x: 170f64,
+            y: 10f64,
+        },
+        Coord {
+            x: -170f64,
+            y: 10f64,
+        },
+        Coord {
+            x: -170f64,
+            y: 0f64,
+        },
+        Coord { x: 180f64, y: 5f64 },
+    ];
+    let voro = match Voronoi::from_coords(&sites) {
+        Ok(ok) => ok,
+        Err(_) => {
+            panic!("cannot proceed");
+        }
+    };
+    match voro.hull_planar() {
+        Some(hull) => {
+            assert_eq!(hull.0.len(), 2);
+            assert!(hull.coords_iter().all(|c| c.x.abs() <= 180f64));
+        }
+        None => panic!("expected a hull"),
+    }
+
+    // A hull around the north pole.
+    let sites = vec![
+        Coord { x: 0f64, y: 60f64 },
+        Coord { x: 72f64, y: 65f64 },
+        Coord {
+            x: 144f64,
+            y: 70f64,
+        },
+        Coord {
+            x: -144f64,
+            y: 62f64,
+        },
+        Coord {
+            x: -72f64,
+            y: 68f64,
+        },
+    ];
+    let voro = match Voronoi::from_coords(&sites) {
+        Ok(ok) => ok,
+        Err(_) => {
+            panic!("cannot proceed");
+        }
+    };
+    match voro.hull_planar() {
+        Some(hull) => {
+            assert_eq!(hull.0.len(), 1);
+            assert!(hull.coords_iter().all(|c| c.x.abs() <= 180f64));
+            assert!(hull.coords_iter().any(|c| c.y == 90f64));
+            assert!(hull.coords_iter().all(|c| c.y != -90f64));
+            assert!(hull.contains(&Point::new(0f64, 89f64)));
+            assert!(hull.contains(&Point::new(100f64, 70f64)));
+            assert!(!hull.contains(&Point::new(0f64, 0f64)));
+        }
+        None => panic!("expected a hull"),
+    }
+}
+
+#[test]
+fn cell_views() {
+    println!("Cell views match the polygons.");
+    // The cells are the faces of a cube, and one more site.
+    let sites = vec![
+        Coord { x: 0f64, y: 0f64 },
+        Coord { x: 90f64, y: 0f64 },
+        Coord { x: 180f64, y: 0f64 },
+        Coord { x: -90f64, y: 0f64 },
+        Coord { x: 0f64, y: 90f64 },
+        Coord { x: 0f64, y: -90f64 },
+        Coord { x: 45f64, y: 30f64 },
+    ];
+    let cube = match Voronoi::from_coords(&sites[..6]) {
+        Ok(ok) => ok,
+        Err(_) => {
+            panic!("cannot proceed");
+        }
+    };
+    for cell in cube.cells() {
+        // The first vertex is not repeated, the ring is closed once.
+        assert_eq!(cell.vertices().len(), 4);
+        assert_eq!(cell.polygon().exterior().0.len(), 5);
+    }
+
+    let mut builder = Voronoi::builder();
+    builder.radius_set(2f64);
+    let mut voro = match builder.build_from_coords(&sites) {
+        Ok(ok) => ok,
+        Err(_) => {
+            panic!("cannot proceed");
+        }
+    };
+    if voro.remove_site(6).is_err() {
+        panic!("cannot proceed");
+    }
+
+    let FeatureCollection(features) = voro.polygons();
+    assert_eq!(voro.cells().len(), features.len());
+    assert!(voro.cell(features.len()).is_none());
+    let pi = std::f64::consts::PI;
+    for (cell, feature) in voro.cells().zip(&features) {
+        let i = cell.site_index();
+        assert_eq!(feature.geometry[0], Geometry::Polygon(cell.polygon()));
+        if i == 6 {
+            assert!(cell.is_degenerate());
+            assert_eq!(cell.vertices().len(), 0);
+            assert_eq!(cell.area(), 0f64);
+            continue;
+        }
+        assert_eq!(cell.site(), sites[i]);
+        assert_eq!(
+            feature.properties[1],
+            FeatureProperty::Sitecoordinates(cell.site())
+        );
+        assert_eq!(
+            feature.properties[2],
+            FeatureProperty::Neighbors(cell.neighbors().to_vec())
+        );
+        assert_eq!(cell.neighbors().len(), 4);
+        assert_eq!(cell.vertices().len(), 4);
+        assert!((cell.area() - 4f64 * 4f64 * pi / 6f64).abs() < 1e-9);
+        assert!(!cell.is_degenerate());
+        assert!(cell.feature().is_none());
+    }
+}
+
+#[test]
+fn finds_in_small_diagrams() {
+    let p = Coord { x: 50f64, y: 50f64 };
+    let two = Voronoi::from_coords(&[
+        Coord { x: 0f64, y: 0f64 },
+        Coord { x: 60f64, y: 60f64 },
+    ])
+    .unwrap();
+    assert_eq!(two.find(&p, Some(10f64)), Some(1));
+    assert_eq!(Voronoi::<f64>::default().find(&p, None), None);
+}
+
 #[test]
 fn link() {
     let sites = Geometry::MultiPoint(MultiPoint(vec![
@@ -422,6 +1286,42 @@ fn links_returns_urquhart_graph() {
     }
 }
 
+#[test]
+fn links_run_from_source_to_target() {
+    println!("geoVoronoi.links() run from source to target.");
+    let sites = Geometry::MultiPoint(MultiPoint(vec![
+        Point::new(0f64, 0f64),
+        Point::new(10f64, 0f64),
+        Point::new(0f64, 10f64),
+        Point::new(10f64, 10f64),
+    ]));
+
+    match Voronoi::links_with_data(sites) {
+        Ok(FeatureCollection(features)) => {
+            assert!(!features.is_empty());
+            for fs in features {
+                let Geometry::LineString(ls) = &fs.geometry[0] else {
+                    panic!("Expected a line string.");
+                };
+                let source = fs.properties.iter().find_map(|p| match p {
+                    FeatureProperty::Source(s) => Some(*s),
+                    _ => None,
+                });
+                let target = fs.properties.iter().find_map(|p| match p {
+                    FeatureProperty::Target(t) => Some(*t),
+                    _ => None,
+                });
+                assert_eq!(ls.0.len(), 2);
+                assert_eq!(Some(ls.0[0]), source);
+                assert_eq!(Some(ls.0[1]), target);
+            }
+        }
+        Err(_) => {
+            panic!("Was expecting a feature collection.")
+        }
+    }
+}
+
 #[test]
 fn links_returns_circumcenters() {
     println!("geoVoronoi.triangles(sites) returns circumcenters.");
@@ -443,7 +1343,7 @@ fn links_returns_circumcenters() {
                     };
                     let w = Coord {
                         x: -180.0_f64 + v.x,
-                        y: -1.0_f64 * v.y,
+                        y: -v.y,
                     };
                     #[allow(clippy::assertions_on_constants)]
                     if ((u.x - v.x).abs() < 1e-6 && (u.y - v.y).abs() < 1e-6)
@@ -490,3 +1390,435 @@ fn does_not_list_fake_points() {
         Some(&(sites.len() - 1usize))
     );
 }
+
+#[test]
+fn feature_properties_follow_their_site() {
+    println!("Feature properties are carried through to every output.");
+    let feature = |x: f64, y: f64, id: f64| Features {
+        properties: vec![FeatureProperty::Length(id)],
+        geometry: vec![Geometry::Point(Point::new(x, y))],
+    };
+    let fc = FeatureCollection(vec![
+        feature(0f64, 0f64, 100f64),
+        feature(f64::NAN, 0f64, 101f64),
+        feature(10f64, 0f64, 102f64),
+        feature(0f64, 10f64, 103f64),
+    ]);
+
+    let gv = match Voronoi::try_from(fc) {
+        Ok(gv) => gv,
+        Err(_) => {
+            panic!("could not proceed");
+        }
+    };
+
+    // The feature with a NaN site is dropped, the others stay aligned.
+    let FeatureCollection(features) = gv.polygons();
+    let expected = [
+        (Coord { x: 0f64, y: 0f64 }, 100f64),
+        (Coord { x: 10f64, y: 0f64 }, 102f64),
+        (Coord { x: 0f64, y: 10f64 }, 103f64),
+    ];
+    assert_eq!(features.len(), expected.len());
+    for (f, (site, id)) in features.iter().zip(expected) {
+        assert_eq!(f.properties[0], FeatureProperty::Site(site));
+        assert_eq!(f.properties[3], FeatureProperty::Length(id));
+    }
+
+    // The features of the vertices, read through their sites.
+    let id_of = |site: usize| match gv.feature(site) {
+        Some(feature) => feature.properties[0].clone(),
+        None => panic!("Did not find a feature."),
+    };
+    let FeatureCollection(triangles) = gv.triangles();
+    let triangle_sites = gv.triangle_sites();
+    assert_eq!(triangle_sites.len(), triangles.len());
+    for (t, sites) in triangles.iter().zip(triangle_sites) {
+        assert_eq!(t.properties.len(), 1);
+        let Geometry::Polygon(polygon) = &t.geometry[0] else {
+            panic!("Was expecting a polygon.");
+        };
+        for (c, site) in polygon.exterior().coords().zip(sites) {
+            assert_eq!(*c, expected[site].0);
+            assert_eq!(id_of(site), FeatureProperty::Length(expected[site].1));
+        }
+    }
+
+    let FeatureCollection(links) = gv.links();
+    let link_sites = gv.link_sites();
+    assert_eq!(link_sites.len(), links.len());
+    for (l, (source, target)) in links.iter().zip(link_sites) {
+        assert_eq!(l.properties.len(), 4);
+        assert_eq!(
+            l.properties[0],
+            FeatureProperty::Source(expected[source].0)
+        );
+        assert_eq!(
+            l.properties[1],
+            FeatureProperty::Target(expected[target].0)
+        );
+        assert_eq!(id_of(source), FeatureProperty::Length(expected[source].1));
+        assert_eq!(id_of(target), FeatureProperty::Length(expected[target].1));
+    }
+}
+
+#[test]
+fn builder_configures_the_diagram() {
+    let sites = Geometry::MultiPoint(MultiPoint(vec![
+        Point::new(0f64, 0f64),
+        Point::new(10f64.to_radians(), 0f64),
+        Point::new(0f64, 10f64.to_radians()),
+    ]));
+
+    let mut builder = Voronoi::builder();
+    builder
+        .units_set(Units::Radians)
+        .radius_set(2f64)
+        .structures_set(Structures {
+            polygons: false,
+            hull: true,
+        });
+    let gv = match builder.build(sites) {
+        Ok(gv) => gv,
+        Err(_) => {
+            panic!("could not proceed");
+        }
+    };
+
+    // The getters read the configuration without consuming the diagram.
+    assert_eq!(gv.units(), Units::Radians);
+    assert_eq!(gv.radius(), 2f64);
+    assert!(!gv.structures().polygons);
+    assert!(gv.delaunay.polygons.is_empty());
+
+    let FeatureCollection(links) = gv.links();
+    let mut lengths: Vec<f64> = links
+        .iter()
+        .map(|l| match l.properties[2] {
+            FeatureProperty::Length(length) => length,
+            _ => {
+                panic!("Did not find a length property.");
+            }
+        })
+        .collect();
+    lengths.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    // On a sphere of radius 2, 10 degrees is 2 * 10 * PI / 180.
+    assert!((lengths[0] - 20f64.to_radians()).abs() < 1e-9);
+}
+
+#[test]
+fn duplicate_sites_share_a_cell() {
+    println!("Coincident stations are merged into one site.");
+    let sites = Geometry::MultiPoint(MultiPoint(vec![
+        Point::new(0f64, 0f64),
+        Point::new(10f64, 0f64),
+        Point::new(0f64, 0f64),
+        Point::new(f64::NAN, 0f64),
+        Point::new(0f64, 10f64),
+        Point::new(10.001f64, 0f64),
+    ]));
+
+    let gv = match Voronoi::try_from(sites.clone()) {
+        Ok(gv) => gv,
+        Err(_) => {
+            panic!("could not proceed");
+        }
+    };
+    assert_eq!(
+        gv.index(),
+        &[Some(0), Some(1), Some(0), None, Some(2), Some(3)]
+    );
+    let FeatureCollection(features) = gv.polygons();
+    assert_eq!(features.len(), 4);
+
+    // On the earth, the last station is 111m away from the second.
+    let mut builder = Voronoi::builder();
+    builder.radius_set(6371f64).tolerance_set(0.5f64);
+    let gv = match builder.build(sites) {
+        Ok(gv) => gv,
+        Err(_) => {
+            panic!("could not proceed");
+        }
+    };
+    assert_eq!(gv.tolerance(), 0.5f64);
+    assert_eq!(gv.site_index(5), Some(1));
+    assert_eq!(gv.site_index(3), None);
+    assert_eq!(gv.inputs(1), &[1, 5]);
+    let FeatureCollection(features) = gv.polygons();
+    assert_eq!(features.len(), 3);
+    assert_eq!(
+        features[1].properties[0],
+        FeatureProperty::Site(Coord { x: 10f64, y: 0f64 })
+    );
+}
+
+#[test]
+fn rebuilds_with_its_configuration() {
+    println!("A diagram rebuilt from new input keeps none of the old one.");
+    let feature = |x: f64, y: f64| Features {
+        properties: vec![FeatureProperty::Length(x)],
+        geometry: vec![Geometry::Point(Point::new(x, y))],
+    };
+    let gv = match Voronoi::try_from(FeatureCollection(vec![
+        feature(f64::NAN, 0f64),
+        feature(0f64, 0f64),
+        feature(10f64, 0f64),
+        feature(0f64, 0f64),
+        feature(0f64, 10f64),
+    ])) {
+        Ok(gv) => gv,
+        Err(_) => {
+            panic!("could not proceed");
+        }
+    };
+    assert_eq!(gv.rejected(), &[(0, Rejection::NonFinite)]);
+    assert_eq!(gv.inputs(0), &[1, 3]);
+
+    let coords = vec![
+        Coord { x: 20f64, y: 0f64 },
+        Coord { x: 0f64, y: 20f64 },
+        Coord { x: 20f64, y: 0f64 },
+        Coord { x: 0f64, y: 0f64 },
+    ];
+    let gv = match gv.with_coords(&coords) {
+        Ok(gv) => gv,
+        Err(_) => {
+            panic!("could not proceed");
+        }
+    };
+    assert_eq!(gv.index(), &[Some(0), Some(1), Some(0), Some(2)]);
+    assert_eq!(gv.inputs(0), &[0, 2]);
+    assert_eq!(gv.inputs(2), &[3]);
+    assert!(gv.rejected().is_empty());
+    assert!(gv.feature(0).is_none());
+    let FeatureCollection(features) = gv.polygons();
+    assert_eq!(features.len(), 3);
+    for (f, site) in features.iter().zip([coords[0], coords[1], coords[3]]) {
+        assert_eq!(f.properties[0], FeatureProperty::Site(site));
+        assert_eq!(f.properties.len(), 3);
+    }
+}
+
+#[test]
+fn constructs_from_coords_points_and_vectors() {
+    println!("The direct constructors agree with MultiPoint input.");
+    let coords = vec![
+        Coord { x: 0f64, y: 0f64 },
+        Coord { x: 90f64, y: 0f64 },
+        Coord { x: 0f64, y: 90f64 },
+        Coord { x: 45f64, y: 30f64 },
+    ];
+    let vectors: Vec<[f64; 3]> = coords
+        .iter()
+        .map(|c| {
+            let (lambda, phi) = (c.x.to_radians(), c.y.to_radians());
+            // Unnormalized, as simulations rarely provide unit vectors.
+            let r = 2f64;
+            [
+                r * phi.cos() * lambda.cos(),
+                r * phi.cos() * lambda.sin(),
+                r * phi.sin(),
+            ]
+        })
+        .collect();
+
+    let polygons = |gv: Voronoi<f64>| {
+        let FeatureCollection(features) = gv.polygons();
+        features
+    };
+    let expected = match Voronoi::try_from(Geometry::MultiPoint(
+        coords.iter().map(|c| Point(*c)).collect(),
+    )) {
+        Ok(gv) => polygons(gv),
+        Err(_) => {
+            panic!("could not proceed");
+        }
+    };
+
+    let from_coords = Voronoi::from_coords(&coords).unwrap();
+    assert_eq!(polygons(from_coords), expected);
+
+    let from_points =
+        Voronoi::from_points(coords.iter().map(|c| Point(*c))).unwrap();
+    assert_eq!(polygons(from_points), expected);
+
+    let from_vectors = Voronoi::from_unit_vectors(&vectors).unwrap();
+    let features = polygons(from_vectors);
+    assert_eq!(features.len(), expected.len());
+    for (f, e) in features.iter().zip(&expected) {
+        let (Geometry::Polygon(a), Geometry::Polygon(b)) =
+            (&f.geometry[0], &e.geometry[0])
+        else {
+            panic!("Was expecting polygons.");
+        };
+        for (a, b) in a.exterior().coords().zip(b.exterior().coords()) {
+            assert!((a.x - b.x).abs() < 1e-9 && (a.y - b.y).abs() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn inserts_and_removes_sites_locally() {
+    println!("Updating a diagram agrees with recomputing it.");
+    let mut coords = vec![
+        Coord { x: 0f64, y: 0f64 },
+        Coord { x: 80f64, y: 10f64 },
+        Coord {
+            x: 170f64,
+            y: -5f64,
+        },
+        Coord { x: -95f64, y: 3f64 },
+        Coord { x: 20f64, y: 85f64 },
+        Coord {
+            x: -40f64,
+            y: -80f64,
+        },
+        Coord {
+            x: 120f64,
+            y: 40f64,
+        },
+        Coord {
+            x: -150f64,
+            y: -35f64,
+        },
+    ];
+    // The centers of each cell, in a canonical order.
+    let cells = |gv: &Voronoi<f64>| -> Vec<Vec<(f64, f64)>> {
+        let d = &gv.delaunay;
+        d.polygons
+            .iter()
+            .map(|polygon| {
+                let mut cell: Vec<(f64, f64)> = polygon
+                    .iter()
+                    .map(|&t| (d.centers[t].x, d.centers[t].y))
+                    .collect();
+                cell.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                cell.dedup();
+                cell
+            })
+            .collect()
+    };
+    let close = |a: &[Vec<(f64, f64)>], b: &[Vec<(f64, f64)>]| {
+        assert_eq!(a.len(), b.len());
+        for (a, b) in a.iter().zip(b) {
+            assert_eq!(a.len(), b.len());
+            for (a, b) in a.iter().zip(b) {
+                assert!((a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9);
+            }
+        }
+    };
+
+    let mut gv = Voronoi::from_coords(&coords).unwrap();
+    let before = cells(&gv);
+    let extra = Coord { x: 10f64, y: 5f64 };
+    let inserted = match gv.insert_site(&extra) {
+        Ok(update) => update,
+        Err(e) => {
+            panic!("could not proceed {e}");
+        }
+    };
+    assert_eq!(inserted.site, 8);
+    assert!(!inserted.rebuilt);
+    assert_eq!(inserted.changed, vec![0, 1, 4, 5, 8]);
+    assert_eq!(gv.site_index(8), Some(8));
+
+    coords.push(extra);
+    let expected = Voronoi::from_coords(&coords).unwrap();
+    close(&cells(&gv), &cells(&expected));
+
+    // The same location is merged into the new site.
+    let merged = gv.insert_site(&extra).unwrap();
+    assert_eq!(merged.site, 8);
+    assert!(merged.changed.is_empty());
+    assert_eq!(gv.inputs(8), &[8, 9]);
+
+    let removed = gv.remove_site(8).unwrap();
+    assert_eq!(removed.changed, inserted.changed);
+    let mut after = cells(&gv);
+    assert!(after.pop().is_some_and(|cell| cell.is_empty()));
+    close(&after, &before);
+    assert_eq!(
+        gv.rejected(),
+        &[(8, Rejection::Removed), (9, Rejection::Removed)]
+    );
+    assert_eq!(gv.site_index(9), None);
+
+    match gv.remove_site(8) {
+        Ok(_) => {
+            panic!("Was expecting an update error.");
+        }
+        Err(e) => {
+            assert_eq!(e, UpdateError::NoSuchSite { site: 8 });
+        }
+    }
+
+    // A non-finite site is refused, and the diagram is unchanged.
+    let nan = Coord {
+        x: f64::NAN,
+        y: 5f64,
+    };
+    match gv.insert_site(&nan) {
+        Ok(_) => {
+            panic!("Was expecting an update error.");
+        }
+        Err(e) => {
+            assert_eq!(e, UpdateError::NonFiniteSite);
+        }
+    }
+    assert_eq!(gv.site_index(10), None);
+    close(&cells(&gv)[..8], &before);
+}
+
+#[test]
+fn rebuilds_keep_the_configured_structures() {
+    println!("An update which rebuilds computes the structures configured.");
+    let coords = vec![
+        Coord { x: 0f64, y: 0f64 },
+        Coord { x: 10f64, y: 0f64 },
+        Coord { x: 0f64, y: 10f64 },
+    ];
+
+    // Too few cells to update locally.
+    let mut builder = Voronoi::builder();
+    builder.structures_set(Structures {
+        polygons: true,
+        hull: false,
+    });
+    let mut gv = match builder.build_from_coords(&coords) {
+        Ok(gv) => gv,
+        Err(_) => {
+            panic!("could not proceed");
+        }
+    };
+    assert!(gv.delaunay.hull.is_empty());
+    let inserted = gv.insert_site(&Coord { x: 5f64, y: 5f64 }).unwrap();
+    assert!(inserted.rebuilt);
+    assert_eq!(gv.delaunay.polygons.len(), 4);
+    assert!(gv.delaunay.hull.is_empty());
+    let removed = gv.remove_site(0).unwrap();
+    assert!(removed.rebuilt);
+    assert!(gv.delaunay.polygons[0].is_empty());
+    assert!(gv.delaunay.hull.is_empty());
+
+    // Without the cells, every update rebuilds.
+    let mut builder = Voronoi::builder();
+    builder.structures_set(Structures {
+        polygons: false,
+        hull: true,
+    });
+    let mut gv = match builder.build_from_coords(&coords) {
+        Ok(gv) => gv,
+        Err(_) => {
+            panic!("could not proceed");
+        }
+    };
+    let inserted = gv.insert_site(&Coord { x: 5f64, y: 5f64 }).unwrap();
+    assert!(inserted.rebuilt);
+    assert!(gv.delaunay.polygons.is_empty());
+    assert!(gv.delaunay.mesh.is_empty());
+    assert_eq!(gv.cells().len(), 0);
+    assert!(!gv.delaunay.hull.is_empty());
+    let removed = gv.remove_site(3).unwrap();
+    assert!(removed.rebuilt);
+    assert!(gv.delaunay.polygons.is_empty());
+}